@@ -8,138 +8,62 @@ impl Soln for Puzzle {
     type Answer = u64;
 
     fn part1(input: &str) -> Self::Answer {
-        let mut lines = input.lines();
-
-        let seeds: Vec<u64> = lines.next().unwrap().trim()
-            .strip_prefix("seeds: ").unwrap()
-            .split(' ')
-            .map(|n| n.parse().unwrap())
-            .collect();
-
-        lines.next().unwrap();
-        let mut maps = Vec::new();
-        while let Some(map) = Map::parse(&mut lines) {
-            maps.push(map);
-        }
-
-        let locs: Vec<u64> = maps.iter()
-            .fold(seeds, |source, map| {
-                source.iter().map(|val| map.convert(*val)).collect()
-            });
-
-        locs.iter().min().copied().unwrap()
+        let (seeds, map) = parse(input);
+        seeds.into_iter().map(|seed| map.get(seed)).min().unwrap()
     }
 
     fn part2(input: &str) -> Self::Answer {
-        let mut lines = input.lines();
-
-        let seeds: Vec<u64> = lines.next().unwrap().trim()
-            .strip_prefix("seeds: ").unwrap()
-            .split(' ')
-            .map(|n| n.parse().unwrap())
-            .collect();
-        let seeds: Vec<Interval<_>> = seeds.chunks(2)
-            .map(|chunk| {
-                match chunk {
-                    &[s, l] => Interval(s, s + l),
-                    _ => panic!(),
-                }
-            }).collect();
-
-        lines.next().unwrap();
-        let mut maps = Vec::new();
-        while let Some(map) = Map::parse(&mut lines) {
-            maps.push(map);
-        }
-
-        let locs: Vec<Interval<_>> = maps.iter()
-            .fold(seeds, |is, map| map.convert_intervals(is));
-
-        locs.iter().min_by_key(|i| i.0).unwrap().0
-    }
-}
-
-#[derive(Debug)]
-struct Map(Vec<(u64, u64, u64)>);
-
-impl Map {
-    fn parse(lines: &mut Lines) -> Option<Self> {
-        lines.next()?;
-
-        let mut ranges = Vec::new();
-        while let Some(range) = lines.next() {
-            let range = range.trim();
-            if range.is_empty() {
-                break;
-            }
+        let (seeds, map) = parse(input);
 
-            let parts: Vec<u64> = range.split(' ').map(|n| n.parse().unwrap()).collect();
-            match parts.as_slice() {
-                &[dest, src, len] => {
-                    ranges.push((src, dest, len));
-                }
+        let mut tree = IntervalTree::new();
+        for chunk in seeds.chunks(2) {
+            match chunk {
+                &[s, l] => tree.insert(Interval(s, s + l)),
                 _ => panic!(),
             }
         }
 
-        Some(Self(ranges))
+        map.map_tree(&tree).pop_first().unwrap().0
     }
+}
 
-    fn convert(&self, val: u64) -> u64 {
-        for (src, dest, len) in &self.0 {
-            if val >= *src && val < *src + *len {
-                return *dest + (val - *src);
-            }
-        }
+/// Parse the seeds and the composition of every map, from seed to location.
+fn parse(input: &str) -> (Vec<u64>, IntervalMap<u64>) {
+    let mut lines = input.lines();
+
+    let seeds = lines.next().unwrap().trim()
+        .strip_prefix("seeds: ").unwrap()
+        .split(' ')
+        .map(|n| n.parse().unwrap())
+        .collect();
 
-        val
+    lines.next().unwrap();
+    let mut map = IntervalMap::new();
+    while let Some(next) = parse_map(&mut lines) {
+        map = map.then(&next);
     }
 
-    fn convert_intervals(&self, is: Vec<Interval<u64>>) -> Vec<Interval<u64>> {
-        let mut res = Vec::new();
-
-        let mut stack = is.clone();
-        while let Some(i @ Interval(s, e)) = stack.pop() {
-            let mut overlaps = 0;
-
-            for (src, dest, len) in &self.0 {
-                let src = Interval(*src, *src + *len);
-                let dest = Interval(*dest, *dest + *len);
-
-                if src.overlaps(i) {
-                    overlaps += 1;
-                } else {
-                    continue;
-                }
-
-                let (s, e) = if s < src.0 && e <= src.1 {
-                    // left overlap
-                    stack.push(Interval(s, src.0));
-                    (src.0, e)
-                } else if s >= src.0 && e > src.1 {
-                    // right overlap
-                    stack.push(Interval(src.1, e));
-                    (s, src.1)
-                } else if s < src.0 && e > src.1 {
-                    // superset
-                    stack.push(Interval(s, src.0));
-                    stack.push(Interval(src.1, e));
-                    (src.0, src.1)
-                } else {
-                    // subset
-                    (s, e)
-                };
-
-                res.push(Interval(dest.0 + (s - src.0), dest.0 + (e - src.0)));
-            }
+    (seeds, map)
+}
 
-            if overlaps == 0 {
-                res.push(i);
-            }
+fn parse_map(lines: &mut Lines) -> Option<IntervalMap<u64>> {
+    lines.next()?;
+
+    let mut map = IntervalMap::new();
+    for range in lines.by_ref() {
+        let range = range.trim();
+        if range.is_empty() {
+            break;
         }
 
-        res
+        let parts: Vec<u64> = range.split(' ').map(|n| n.parse().unwrap()).collect();
+        match parts.as_slice() {
+            &[dest, src, len] => map.insert(Interval(src, src + len), dest),
+            _ => panic!(),
+        }
     }
+
+    Some(map)
 }
 
 #[cfg(test)]
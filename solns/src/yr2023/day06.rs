@@ -1,4 +1,5 @@
 use crate::Soln;
+use utils::prelude::*;
 
 pub struct Puzzle;
 impl Soln for Puzzle {
@@ -27,8 +28,8 @@ impl Soln for Puzzle {
 }
 
 struct Race {
-    time: f64,
-    record: f64,
+    time: i64,
+    record: i64,
 }
 
 impl Race {
@@ -36,13 +37,13 @@ impl Race {
         let lines: Vec<&str> = input.lines().collect();
 
         let (_, times) = lines[0].split_once(':').unwrap();
-        let times: Vec<f64> = times.trim()
+        let times: Vec<i64> = times.trim()
             .split_whitespace()
             .map(|n| n.parse().unwrap())
             .collect();
 
         let (_, dists) = lines[1].split_once(':').unwrap();
-        let dists: Vec<f64> = dists.trim()
+        let dists: Vec<i64> = dists.trim()
             .split_whitespace()
             .map(|n| n.parse().unwrap())
             .collect();
@@ -52,12 +53,10 @@ impl Race {
             .collect()
     }
 
+    /// Holding the button for `x` ms travels `x * (time - x)` mm, so count
+    /// the `x` with `-x² + time·x - record > 0`.
     fn ways_to_win(&self) -> i64 {
-        let discrt = (self.time.powi(2) - 4.0*self.record).sqrt();
-        let min = 1 + ((self.time - discrt) / 2.0).floor() as i64;
-        let max = -1 + ((self.time + discrt) / 2.0).ceil() as i64;
-
-        max - min + 1
+        math::quadratic_gt(-1, self.time, -self.record).len()
     }
 }
 
@@ -31,36 +31,12 @@ impl Soln for Puzzle {
     fn part2(input: &str) -> Self::Answer {
         let (steps, nodes) = parse(input);
 
-        let mut cycles = HashMap::new();
-        for start in nodes.values() {
-            let start_label = start.label.clone();
-            let mut node = start;
-            for (n, step) in steps.chars().cycle().enumerate() {
-                let next = match step {
-                    'L' => nodes.get(&node.left).unwrap(),
-                    'R' => nodes.get(&node.right).unwrap(),
-                    _ => panic!(),
-                };
-
-                if next.label.ends_with('Z') {
-                    let end_label = next.label.clone();
-                    let steps = (n + 1).try_into().unwrap();
-                    cycles.insert(start_label, Cycle { end_label, steps });
-                    break;
-                } else if next == node {
-                    break;
-                } else {
-                    node = next;
-                }
-            }
-        }
+        let cycles: Vec<_> = nodes.values()
+            .filter(|node| node.label.ends_with('A'))
+            .map(|start| Cycle::find(&steps, &nodes, start))
+            .collect();
 
-        // shamelessly taking advantage of the fact that each A->Z cycle in the input is the
-        // same length as the corresponding Z->Z self-cycle
-        cycles.iter()
-            .filter(|(start, _)| start.ends_with('Z'))
-            .map(|(_, Cycle {steps, ..})| steps)
-            .fold(1, |a, &b| math::lcm(a, b))
+        Cycle::first_meeting(&cycles).expect("ghosts never line up on Z nodes")
     }
 }
 
@@ -80,10 +56,85 @@ fn parse(input: &str) -> (String, HashMap<String, Node>) {
     (steps.into(), nodes)
 }
 
+/// The path of a ghost, which is eventually periodic since its state is just
+/// the current node and position in the step list.
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct Cycle {
+    offset: u64,
     steps: u64,
-    end_label: Label,
+    /// Times at which the ghost is on a Z node before it enters the cycle.
+    prefix_hits: Vec<u64>,
+    /// Times in `offset..offset + steps` at which the ghost is on a Z node,
+    /// each repeating every `steps`.
+    hits: Vec<u64>,
+}
+
+impl Cycle {
+    /// Follow the path from `start` until a (node, step index) state repeats.
+    fn find(steps: &str, nodes: &HashMap<String, Node>, start: &Node) -> Self {
+        let steps: Vec<char> = steps.chars().collect();
+        let mut seen = HashMap::new();
+        let mut z_times = vec![];
+
+        let mut node = start;
+        for n in 0.. {
+            let i = n as usize % steps.len();
+            if let Some(&offset) = seen.get(&(node.label.as_str(), i)) {
+                let (prefix_hits, hits) = z_times.into_iter()
+                    .partition(|&t| t < offset);
+                return Self { offset, steps: n - offset, prefix_hits, hits };
+            }
+            seen.insert((node.label.as_str(), i), n);
+
+            if node.label.ends_with('Z') {
+                z_times.push(n);
+            }
+
+            node = match steps[i] {
+                'L' => nodes.get(&node.left).unwrap(),
+                'R' => nodes.get(&node.right).unwrap(),
+                _ => panic!(),
+            };
+        }
+
+        unreachable!()
+    }
+
+    fn at_z(&self, t: u64) -> bool {
+        if t < self.offset {
+            self.prefix_hits.contains(&t)
+        } else {
+            self.hits.iter().any(|&hit| (t - hit) % self.steps == 0)
+        }
+    }
+
+    /// Get the first time every ghost is on a Z node at once, if ever.
+    fn first_meeting(cycles: &[Self]) -> Option<u64> {
+        // a meeting before some ghost has entered its cycle must be one of
+        // that ghost's prefix hits
+        let early = cycles.iter()
+            .flat_map(|cycle| cycle.prefix_hits.iter().copied())
+            .filter(|&t| cycles.iter().all(|cycle| cycle.at_z(t)))
+            .min();
+
+        // otherwise pick one hit from each cycle and line them up
+        let mut congruences = vec![(0, 1)];
+        for cycle in cycles {
+            congruences = congruences.iter()
+                .flat_map(|&acc| cycle.hits.iter().map(move |&hit| (acc, hit)))
+                .filter_map(|(acc, hit)| math::crt(acc, (hit % cycle.steps, cycle.steps)))
+                .collect();
+        }
+
+        let start = cycles.iter().map(|cycle| cycle.offset).max().unwrap_or(0);
+        let late = congruences.into_iter()
+            .map(|(t, period)| {
+                if t >= start { t } else { t + (start - t).div_ceil(period) * period }
+            })
+            .min();
+
+        early.into_iter().chain(late).min()
+    }
 }
 
 impl Ord for Cycle {
@@ -149,4 +200,31 @@ mod tests {
              XXX = (XXX, XXX)";
         assert_eq!(6, Puzzle::part2(input));
     }
+
+    #[test]
+    fn ghosts_with_several_z_nodes() {
+        let input =
+            "L
+
+             1A = (1B, XXX)
+             1B = (1Z, XXX)
+             1Z = (1B, XXX)
+             2A = (PZ, XXX)
+             PZ = (QZ, XXX)
+             QZ = (2B, XXX)
+             2B = (PZ, XXX)
+             XXX = (XXX, XXX)";
+        assert_eq!(2, Puzzle::part2(input));
+    }
+
+    #[test]
+    fn ghosts_never_meet() {
+        let (steps, nodes) = parse("LR\n\n1A = (1Z, XXX)\n1Z = (XXX, XXX)\nXXX = (XXX, XXX)");
+        let cycle = Cycle::find(&steps, &nodes, &nodes["1A"]);
+        assert_eq!((vec![1], vec![]), (cycle.prefix_hits.clone(), cycle.hits.clone()));
+        assert_eq!(Some(1), Cycle::first_meeting(&[cycle.clone()]));
+
+        let stuck = Cycle::find(&steps, &nodes, &nodes["XXX"]);
+        assert_eq!(None, Cycle::first_meeting(&[cycle, stuck]));
+    }
 }
@@ -1,5 +1,3 @@
-use std::iter;
-
 use crate::Soln;
 use utils::prelude::*;
 
@@ -10,59 +8,25 @@ impl Soln for Puzzle {
     type Answer = Num;
 
     fn part1(input: &str) -> Self::Answer {
-        extrapolate(input, |acc, diffs| diffs.last() + acc)
+        parse(input)
+            .map(|Hist(hist)| math::extrapolate(&hist, hist.len() as i64))
+            .sum()
     }
 
     fn part2(input: &str) -> Self::Answer {
-        extrapolate(input, |acc, diffs| diffs.first() - acc)
+        parse(input)
+            .map(|Hist(hist)| math::extrapolate(&hist, -1))
+            .sum()
     }
 }
 
-fn extrapolate<F>(input: &str, f: F) -> Num
-where F: Fn(Num, &Hist) -> Num
-{
-    input.lines()
-        .map(|line| Hist::successors(line.parse().unwrap())
-            .iter().rev()
-            .fold(0, &f))
-        .sum()
+fn parse(input: &str) -> impl Iterator<Item = Hist> + '_ {
+    input.lines().map(|line| line.parse().unwrap())
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, FromWords)]
 struct Hist(Vec<Num>);
 
-impl Hist {
-    fn successors(start: Self) -> Vec<Self> {
-        iter::successors(Some(start), |prev| {
-            let next = prev.diffs();
-            if next.is_const() {
-                None
-            } else {
-                Some(next)
-            }
-        }).collect()
-    }
-
-    fn diffs(&self) -> Self {
-        Self(self.0.as_slice()
-            .windows(2)
-            .map(|w| w[1] - w[0])
-            .collect())
-    }
-
-    fn is_const(&self) -> bool {
-        self.0.iter().all(|&x| x == 0)
-    }
-
-    fn first(&self) -> Num {
-        self.0[0]
-    }
-
-    fn last(&self) -> Num {
-        *self.0.last().unwrap()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,4 @@
-use std::ops::{Index, IndexMut};
+use std::ops::Index;
 
 use crate::Soln;
 use utils::prelude::*;
@@ -6,8 +6,7 @@ use utils::prelude::*;
 const START: &'static str = "in";
 
 const MIN_RATING: Rating = 1;
-const MAX_RATING: Rating = 4001;
-const RATING_INT: Interval<Rating> = Interval(MIN_RATING, MAX_RATING);
+const MAX_RATING: Rating = 4000;
 
 type Rating = u64;
 
@@ -38,16 +37,16 @@ impl Soln for Puzzle {
         let start = workflows.get(&START).unwrap();
 
         let mut valid = vec![];
-        let mut stack = vec![(PartCombos::new(), start)];
+        let all = PartCombos::cube(Interval::closed(MIN_RATING, MAX_RATING));
+        let mut stack = vec![(all, start)];
         while let Some((parts, workflow)) = stack.pop() {
             workflow.rules.iter()
                 .fold(Some(parts), |acc, rule| {
                     acc.and_then(|acc| {
-                        let split = acc.split(rule);
+                        let (accept, reject) = rule.split(&acc);
 
-                        let (accept, action) = split.accept;
-                        if !accept.is_empty() {
-                            match action {
+                        if let Some(accept) = accept {
+                            match rule.action() {
                                 Action::Send(new) => {
                                     let new = workflows.get(new).unwrap();
                                     stack.push((accept, new));
@@ -57,13 +56,13 @@ impl Soln for Puzzle {
                             };
                         }
 
-                        split.reject
+                        reject
                     })
                 });
         }
 
         valid.iter()
-            .map(PartCombos::score)
+            .map(PartCombos::volume)
             .sum()
     }
 }
@@ -141,6 +140,29 @@ impl<'a> Rule<'a> {
             Self::Unchecked(action) => Some(action),
         }
     }
+
+    fn action(&self) -> &Action<'a> {
+        match self {
+            Self::Checked{action, ..} | Self::Unchecked(action) => action,
+        }
+    }
+
+    /// Split a set of parts into those matching the rule and the rest.
+    fn split(&self, parts: &PartCombos) -> (Option<PartCombos>, Option<PartCombos>) {
+        match self {
+            Self::Checked{cat, oper, val, ..} => {
+                let axis = cat.axis();
+                match oper {
+                    Oper::Lt => parts.split(axis, *val),
+                    Oper::Gt => {
+                        let (below, above) = parts.split(axis, val + 1);
+                        (above, below)
+                    },
+                }
+            },
+            Self::Unchecked(_) => (Some(*parts), None),
+        }
+    }
 }
 
 impl<'a> fmt::Display for Rule<'a> {
@@ -171,96 +193,8 @@ impl<'a> From<&'a str> for Rule<'a> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct PartCombos {
-    x: Interval<Rating>,
-    m: Interval<Rating>,
-    a: Interval<Rating>,
-    s: Interval<Rating>,
-}
-
-struct Split<'a> {
-    accept: (PartCombos, &'a Action<'a>),
-    reject: Option<PartCombos>,
-}
-
-impl PartCombos {
-    fn new() -> Self {
-        Self {
-            x: RATING_INT,
-            m: RATING_INT,
-            a: RATING_INT,
-            s: RATING_INT,
-        }
-    }
-
-    fn is_empty(&self) -> bool {
-        self.x.is_empty()
-            || self.m.is_empty()
-            || self.a.is_empty()
-            || self.s.is_empty()
-    }
-
-    fn score(&self) -> Rating {
-        self.x.len() * self.m.len() * self.a.len() * self.s.len()
-    }
-
-    fn with_cat(&self, cat: Cat, val: Interval<Rating>) -> Self {
-        let mut new = self.clone();
-        new[cat] = val;
-        new
-    }
-
-    fn split<'a>(self, rule: &'a Rule) -> Split<'a> {
-        match rule {
-            Rule::Checked {cat, oper, val, action} => {
-                let Interval(min, max) = self[*cat];
-                let (accept, reject) = match oper {
-                    Oper::Lt => (Interval(min, *val), Interval(*val, max)),
-                    Oper::Gt => (Interval(val+1, max), Interval(min, val+1)),
-                };
-
-                let accept = (self.with_cat(*cat, accept), action);
-                let reject = Some(self.with_cat(*cat, reject));
-                Split {accept, reject}
-            },
-            Rule::Unchecked(action) => {
-                let accept = (self, action);
-                let reject = None;
-                Split {accept, reject}
-            },
-        }
-    }
-}
-
-impl fmt::Display for PartCombos {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{{x={}, m={}, a={}, s={}}}", self.x, self.m, self.a, self.s)
-    }
-}
-
-impl Index<Cat> for PartCombos {
-    type Output = Interval<Rating>;
-    fn index(&self, index: Cat) -> &Self::Output {
-        match index {
-            Cat::Xtreme => &self.x,
-            Cat::Musical => &self.m,
-            Cat::Aero => &self.a,
-            Cat::Shiny => &self.s,
-        }
-    }
-}
-
-impl IndexMut<Cat> for PartCombos {
-    fn index_mut(&mut self, index: Cat) -> &mut Self::Output {
-        match index {
-            Cat::Xtreme => &mut self.x,
-            Cat::Musical => &mut self.m,
-            Cat::Aero => &mut self.a,
-            Cat::Shiny => &mut self.s,
-        }
-    }
-}
+/// Ranges of ratings for each category, indexed by `Cat::axis`.
+type PartCombos = HyperRect<Rating, 4>;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct Part {
@@ -358,6 +292,17 @@ enum Cat {
     Shiny = b's',
 }
 
+impl Cat {
+    fn axis(&self) -> usize {
+        match *self {
+            Cat::Xtreme => 0,
+            Cat::Musical => 1,
+            Cat::Aero => 2,
+            Cat::Shiny => 3,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
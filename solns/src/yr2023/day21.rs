@@ -14,22 +14,23 @@ impl Soln for Puzzle {
 
     fn part2(input: &str) -> Self::Answer {
         let grid: Vec2D<Cell> = input.parse().unwrap();
-        reachable_plots_inf(&grid, 26501365)
+
+        // the start is in the middle of a clear row and column, so the frontier
+        // crosses one more copy of the (square) garden every `width` steps, and
+        // the number of reachable plots grows quadratically in the copies crossed
+        let steps = 26501365;
+        let width = grid.dim().1 as u64;
+        let plots: i64 = math::extrapolate_samples(steps % width, width, 2, steps, |n| {
+            reachable_plots_inf(&grid, n as usize) as i64
+        });
+
+        plots as usize
     }
 }
 
 fn reachable_plots(grid: &Vec2D<Cell>, steps: usize) -> usize {
-    let Dim(h, w) = grid.dim();
-
-    let mut front = HashSet::new();
-    for row in 1..h {
-        for col in 1..w {
-            if grid[Point(row, col)] == Start {
-                front.insert(Point(row, col));
-                break;
-            }
-        }
-    }
+    let start = grid.position(|&cell| cell == Start).unwrap();
+    let mut front = HashSet::from([start]);
 
     for _ in 0..steps {
         let front2 = front.drain()
@@ -45,17 +46,10 @@ fn reachable_plots(grid: &Vec2D<Cell>, steps: usize) -> usize {
 }
 
 fn reachable_plots_inf(grid: &Vec2D<Cell>, steps: usize) -> usize {
-    let dim@Dim(h, w) = grid.dim();
-
-    let mut front = HashSet::new();
-    for row in 1..h {
-        for col in 1..w {
-            if grid[Point(row, col)] == Start {
-                front.insert(Point(row as isize, col as isize));
-                break;
-            }
-        }
-    }
+    let dim = grid.dim();
+
+    let start = grid.position(|&cell| cell == Start).unwrap();
+    let mut front = HashSet::from([start.cast::<isize>().unwrap()]);
 
     let mut seen = front.clone();
     let mut front2 = HashSet::new();
@@ -116,7 +110,7 @@ fn point_inf(pt: Point<isize>, dim: Dim) -> IdxPoint {
         c => c,
     };
 
-    Point(row as usize, col as usize)
+    Point(row, col).cast().unwrap()
 }
 
 
@@ -1,5 +1,3 @@
-use std::ops::Index;
-
 use crate::Soln;
 use utils::prelude::*;
 
@@ -23,128 +21,40 @@ fn parse(input: &str) -> Vec<Brick> {
         .map(|line| line.into())
         .collect();
 
-    bricks.sort_by_key(|brick| brick.origin().2);
+    bricks.sort_by_key(|brick| brick.bottom());
     bricks
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Brick {
-    Cube(Point3d),
-    Rect(Point3d, Axis3d, usize),
-}
-use Brick::*;
+struct Brick(Cuboid<usize>);
 
 impl Brick {
-    fn on_axis(from: Point3d, axis: Axis3d, to: Point3d) -> Self {
-        Self::Rect(from, axis, from.dist_on_axis(axis, to))
-    }
-
-    fn origin(self) -> Point3d {
-        match self {
-            Self::Cube(o) => o,
-            Self::Rect(o, _, _) => o,
-        }
-    }
-
-    fn map<F: Fn(Point3d) -> Point3d>(self, f: F) -> Self {
-        match self {
-            Self::Cube(o) => Self::Cube(f(o)),
-            Self::Rect(o, a, s) => Self::Rect(f(o), a, s),
-        }
+    fn bottom(self) -> usize {
+        self.0.min[Z]
     }
 
     fn at_height(self, height: usize) -> Self {
-        self.map(|o| Point3d(o.0, o.1, height))
+        let Cuboid { min, max } = self.0;
+        let top = height + self.0.len(Z) - 1;
+        Self(Cuboid { min: Point3(min.0, min.1, height), max: Point3(max.0, max.1, top) })
     }
 
     fn overlaps(self, other: Self) -> bool {
-        match (self, other) {
-            (Self::Cube(p), Self::Cube(q)) => p == q,
-            (Self::Cube(p), Self::Rect(q, a, s))
-                | (Self::Rect(q, a, s), Self::Cube(p)) =>
-                p.on_axis(a, q) && p[a].clamp(q[a], q[a] + s) == p[a],
-            (Self::Rect(p, a, s), Self::Rect(q, b, t)) => {
-                if a == Z && b == Z {
-                    p.on_axis(Z, q) &&
-                        cmp::max(p[Z], q[Z]) <= cmp::min(p[Z] + s, q[Z] + t)
-                } else if a == Z {
-                    todo!()
-                } else {
-                    todo!()
-                }
-            }
-        }
+        self.0.overlaps(&other.0)
     }
 }
 
 impl From<&str> for Brick {
     fn from(s: &str) -> Self {
         let ends = s.split_once('~').unwrap();
-        let from: Point3d = ends.0.into();
-        let to: Point3d = ends.1.into();
-
-        if from == to {
-            Self::Cube(from)
-        } else if from.on_axis(X, to) {
-            Self::on_axis(from, X, to)
-        } else if from.on_axis(Y, to) {
-            Self::on_axis(from, Y, to)
-        } else if from.on_axis(Z, to) {
-            Self::on_axis(from, Z, to)
-        } else {
-            panic!()
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Point3d(usize, usize, usize);
-
-impl Point3d {
-    fn on_axis(self, axis: Axis3d, other: Self) -> bool {
-        match axis {
-            X => self[Y] == other[Y] && self[Z] == other[Z],
-            Y => self[X] == other[X] && self[Z] == other[Z],
-            Z => self[X] == other[X] && self[Y] == other[Y],
-        }
-    }
-
-    fn dist_on_axis(self, axis: Axis3d, other: Self) -> usize {
-        other[axis] - self[axis] + 1
-    }
-}
-
-impl Index<Axis3d> for Point3d {
-    type Output = usize;
-    fn index(&self, index: Axis3d) -> &Self::Output {
-        match index {
-            X => &self.0,
-            Y => &self.1,
-            Z => &self.2,
-        }
-    }
-}
+        let from: Point3<usize> = ends.0.parse().unwrap();
+        let to: Point3<usize> = ends.1.parse().unwrap();
 
-impl From<&str> for Point3d {
-    fn from(s: &str) -> Self {
-        let parts: Vec<usize> = s.split(',')
-            .map(|w| w.parse().unwrap())
-            .collect();
-
-        match parts.as_slice() {
-            &[x, y, z] => Self(x, y, z),
-            _ => panic!(),
-        }
+        Self(Cuboid::new(from, to))
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Axis3d {
-    X,
-    Y,
-    Z,
-}
-use Axis3d::*;
+use Axis3::*;
 
 #[cfg(test)]
 mod tests {
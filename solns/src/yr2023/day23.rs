@@ -79,10 +79,7 @@ impl TryFrom<char> for Cell {
         match ch {
             '.' => Ok(Path),
             '#' => Ok(Forest),
-            '>' => Ok(Slope(E)),
-            '<' => Ok(Slope(W)),
-            '^' => Ok(Slope(N)),
-            'v' => Ok(Slope(S)),
+            '^' | 'v' | '<' | '>' => Direction::try_from(ch).map(Slope),
             _ => Err(())
         }
     }
@@ -95,12 +92,12 @@ mod tests {
     #[test]
     #[ignore]
     fn part1() {
-        assert_eq!((), Puzzle::part1(""));
+        assert_eq!(0, Puzzle::part1(""));
     }
 
     #[test]
     #[ignore]
     fn part2() {
-        assert_eq!((), Puzzle::part2(""));
+        assert_eq!(0, Puzzle::part2(""));
     }
 }
@@ -3,14 +3,119 @@ use utils::prelude::*;
 
 pub struct Puzzle;
 impl Soln for Puzzle {
-    type Answer = ();
+    type Answer = usize;
 
-    fn part1(_input: &str) -> Self::Answer {
-        unsolved!()
+    fn part1(input: &str) -> Self::Answer {
+        let hail = parse(input);
+        crossings_within(&hail, 200000000000000, 400000000000000)
     }
 
-    fn part2(_input: &str) -> Self::Answer {
-        unsolved!()
+    fn part2(input: &str) -> Self::Answer {
+        let hail = parse(input);
+        let rock = throw_rock(&hail).unwrap();
+        (rock.pos.0 + rock.pos.1 + rock.pos.2) as usize
+    }
+}
+
+fn parse(input: &str) -> Vec<Hailstone> {
+    input.lines()
+        .map(|line| line.trim().parse().unwrap())
+        .collect()
+}
+
+/// Count the pairs of hailstones whose paths cross in the future inside the
+/// test area, ignoring the z axis.
+fn crossings_within(hail: &[Hailstone], min: i64, max: i64) -> usize {
+    let area = Rect::new(Point(min, min), Point(max, max));
+
+    let mut count = 0;
+    for (i, a) in hail.iter().enumerate() {
+        for b in &hail[i+1..] {
+            if let Intersection::Point(p) = a.path_xy().intersection(&b.path_xy()) {
+                if p.within(&area) {
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    count
+}
+
+/// Find the position and velocity of a rock that hits every hailstone.
+///
+/// The textbook system from `(pos - p_i) × (vel - v_i) = 0` has minors around
+/// 10^52 for real inputs, which overflows `i128`. So first find two hit times
+/// geometrically, which leaves a small system to solve.
+fn throw_rock(hail: &[Hailstone]) -> Option<Hailstone> {
+    // work relative to the first hailstone, which then sits still at the origin
+    let wide = |p: Point3<i64>| Point3(p.0 as i128, p.1 as i128, p.2 as i128);
+    let (&first, rest) = hail.split_first()?;
+    let (p0, v0) = (wide(first.pos), wide(first.vel));
+    let rel: Vec<_> = rest.iter()
+        .map(|stone| (wide(stone.pos) - p0, wide(stone.vel) - v0))
+        .collect();
+
+    // the rock passes through the origin and crosses the path of the next
+    // hailstone, so it moves within the plane containing both
+    let (&(p1, v1), others) = rel.split_first()?;
+    let normal = p1.cross(v1);
+
+    // any other two hailstones are hit where (and when) they cross the plane
+    let hits: Vec<_> = others.iter()
+        .filter(|(_, v)| v.dot(normal) != 0)
+        .take(2)
+        .map(|&(p, v)| {
+            let (num, den) = (-p.dot(normal), v.dot(normal));
+            let t = if num % den == 0 { Some(num / den) } else { None }?;
+            Some((t, p + v * t))
+        })
+        .collect::<Option<_>>()?;
+
+    // then the rock's position and velocity satisfy pos + t·vel = q for both
+    // hits, six linear equations in six unknowns
+    let mut a = vec![];
+    let mut b = vec![];
+    for (t, q) in hits {
+        for (i, axis) in Axis3::ALL.into_iter().enumerate() {
+            let mut row = vec![math::Ratio::zero(); 6];
+            row[i] = math::Ratio::one();
+            row[i + 3] = t.into();
+            a.push(row);
+            b.push(q[axis].into());
+        }
+    }
+
+    let math::Solution::Unique(x) = math::solve(&a, &b) else { return None };
+    let x: Vec<i128> = x.iter().map(math::Ratio::to_integer).collect::<Option<_>>()?;
+    let (pos, vel) = (Point3(x[0], x[1], x[2]), Point3(x[3], x[4], x[5]));
+
+    let narrow = |p: Point3<i128>| {
+        Some(Point3(p.0.try_into().ok()?, p.1.try_into().ok()?, p.2.try_into().ok()?))
+    };
+    Some(Hailstone { pos: narrow(pos + p0)?, vel: narrow(vel + v0)? })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Hailstone {
+    pos: Point3<i64>,
+    vel: Point3<i64>,
+}
+
+impl Hailstone {
+    fn path_xy(&self) -> Ray<i64> {
+        Ray {
+            origin: Point(self.pos.0, self.pos.1),
+            dir: Point(self.vel.0, self.vel.1),
+        }
+    }
+}
+
+impl FromStr for Hailstone {
+    type Err = ParsePointError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pos, vel) = s.split_once('@').ok_or(ParsePointError)?;
+        Ok(Self { pos: pos.parse()?, vel: vel.parse()? })
     }
 }
 
@@ -18,15 +123,42 @@ impl Soln for Puzzle {
 mod tests {
     use super::*;
 
+    static INPUT: &str =
+        "19, 13, 30 @ -2,  1, -2
+         18, 19, 22 @ -1, -1, -2
+         20, 25, 34 @ -2, -2, -4
+         12, 31, 28 @ -1, -2, -1
+         20, 19, 15 @  1, -5, -3";
+
     #[test]
-    #[ignore]
     fn part1() {
-        assert_eq!((), Puzzle::part1(""));
+        let hail = parse(INPUT);
+        assert_eq!(2, crossings_within(&hail, 7, 27));
     }
 
     #[test]
-    #[ignore]
     fn part2() {
-        assert_eq!((), Puzzle::part2(""));
+        assert_eq!(47, Puzzle::part2(INPUT));
+    }
+
+    #[test]
+    fn throw_rock_large() {
+        // input-sized coordinates, to check the exact arithmetic doesn't overflow
+        let rock = Hailstone {
+            pos: Point3(287_430_900_705_823, 451_620_998_712_421, 260_730_677_041_648),
+            vel: Point3(-54, -265, 43),
+        };
+        let stones = [
+            (Point3(-122, 148, -60), 714_352_192_408),
+            (Point3(71, -27, 239), 96_017_422_601),
+            (Point3(-193, -3, 15), 488_210_736_912),
+            (Point3(208, -175, -171), 321_905_512_077),
+            (Point3(17, 91, 140), 650_118_027_734),
+        ];
+        let hail: Vec<_> = stones.iter()
+            .map(|&(vel, t)| Hailstone { pos: rock.pos + (rock.vel - vel) * t, vel })
+            .collect();
+
+        assert_eq!(Some(rock), throw_rock(&hail));
     }
 }
@@ -25,11 +25,11 @@ pub trait AocString {
     /// each part to the given type.
     fn split_into<'a, T, P>(&'a self, pat: P) -> impl Iterator<Item = T>
         where T: FromStr,
-              P: Pattern<'a>;
+              P: Pattern;
 
     fn strip_circumfix<'a, P>(&'a self, pre: P, post: P) -> Option<&'a Self>
-        where P: Pattern<'a>,
-              <P as Pattern<'a>>::Searcher: ReverseSearcher<'a>;
+        where P: Pattern,
+              for<'b> <P as Pattern>::Searcher<'b>: ReverseSearcher<'b>;
 
     fn strip_braces(&self) -> Option<&Self> {
         self.strip_circumfix('{', '}')
@@ -51,14 +51,14 @@ impl AocString for str {
 
     fn split_into<'a, T, P>(&'a self, pat: P) -> impl Iterator<Item = T>
     where T: FromStr,
-          P: Pattern<'a>,
+          P: Pattern,
     {
         self.split(pat).filter_map(|word| word.parse().ok())
     }
 
     fn strip_circumfix<'a, P>(&'a self, pre: P, post: P) -> Option<&'a str>
-        where P: Pattern<'a>,
-              <P as Pattern<'a>>::Searcher: ReverseSearcher<'a>,
+        where P: Pattern,
+              for<'b> <P as Pattern>::Searcher<'b>: ReverseSearcher<'b>,
     {
         self.strip_prefix(pre)?
             .strip_suffix(post)
@@ -1,11 +1,20 @@
-//! Fundamental 2D geometric primitives.
+//! Fundamental 2D and 3D geometric primitives.
 
-use std::ops::{Add, Sub};
-use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, Div, Neg, Index, IndexMut};
+use std::cmp::{self, Ordering};
 use std::fmt;
+use std::str::FromStr;
 
 use num_traits::{NumCast, Float, PrimInt, Signed};
 
+mod line;
+mod raster;
+mod ball;
+mod hex;
+pub use line::*;
+pub use raster::*;
+pub use hex::*;
+
 pub static NEIGHBORS: [Direction; 8] = [
     Direction::NW, Direction::N, Direction::NE,
     Direction::W,                Direction::E,
@@ -16,6 +25,16 @@ pub static CARDINAL_DIRS: [Direction; 4] = [
     Direction::N, Direction::E, Direction::S, Direction::W
 ];
 
+pub static DIAGONAL_DIRS: [Direction; 4] = [
+    Direction::NE, Direction::SE, Direction::SW, Direction::NW
+];
+
+/// All directions in clockwise order, starting from north.
+pub static CLOCKWISE_DIRS: [Direction; 8] = [
+    Direction::N, Direction::NE, Direction::E, Direction::SE,
+    Direction::S, Direction::SW, Direction::W, Direction::NW,
+];
+
 /// A general-purpose lattice point. Order: (row, col)
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Point<T>(pub T, pub T);
@@ -91,6 +110,48 @@ where T: PrimInt {
     pub fn colinear(&self, other: &Self) -> bool {
         (self.0 == other.0) ^ (self.1 == other.1)
     }
+
+    pub fn manhattan(self, other: Self) -> T {
+        let Self(dr, dc) = self.abs_diff(other);
+        dr + dc
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        let Self(dr, dc) = self.abs_diff(other);
+        cmp::max(dr, dc)
+    }
+
+    /// Get the componentwise distance between two points (works for unsigned
+    /// points too).
+    pub fn abs_diff(self, other: Self) -> Self {
+        let diff = |a: T, b: T| if a > b { a - b } else { b - a };
+        Self(diff(self.0, other.0), diff(self.1, other.1))
+    }
+
+    /// Move one step in the given direction, or `None` if that would
+    /// overflow (e.g. stepping north or west from row or column 0 of an
+    /// `IdxPoint`).
+    pub fn checked_step(self, dir: Direction) -> Option<Self> {
+        self.checked_steps(dir, T::one())
+    }
+
+    /// Move `n` steps in the given direction, or `None` on overflow.
+    pub fn checked_steps(self, dir: Direction, n: T) -> Option<Self> {
+        let step = |x: T, d: i8| match d {
+            -1 => x.checked_sub(&n),
+             1 => x.checked_add(&n),
+             _ => Some(x),
+        };
+
+        let Point(dr, dc) = dir.unit::<i8>();
+        Some(Self(step(self.0, dr)?, step(self.1, dc)?))
+    }
+
+    /// Convert to a point with a different coordinate type, or `None` if
+    /// either coordinate is out of range (e.g. negative for `IdxPoint`).
+    pub fn cast<U: PrimInt>(self) -> Option<Point<U>> {
+        Some(Point(U::from(self.0)?, U::from(self.1)?))
+    }
 }
 
 impl<T> Point<T>
@@ -111,6 +172,15 @@ where T: PrimInt + Signed {
         Self(self.0.signum(), self.1.signum())
     }
 
+    /// Rotate a quarter turn about the origin. Matches `Direction::rot90`, so
+    /// that `dir.unit().rot90(rot) == dir.rot90(rot).unit()`.
+    pub fn rot90(self, rot: Rot) -> Self {
+        match rot {
+            Rot::Pos => Self(-self.1, self.0),
+            Rot::Neg => Self(self.1, -self.0),
+        }
+    }
+
     pub fn direction(&self) -> Option<Direction> {
         let one = T::one();
         let zero = T::zero();
@@ -154,6 +224,56 @@ where T: PrimInt {
     }
 }
 
+impl<T> AddAssign for Point<T>
+where T: PrimInt {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> SubAssign for Point<T>
+where T: PrimInt {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> Mul<T> for Point<T>
+where T: PrimInt {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<T> Div<T> for Point<T>
+where T: PrimInt {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output {
+        Self(self.0 / rhs, self.1 / rhs)
+    }
+}
+
+impl<T> Neg for Point<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((row, col): (T, T)) -> Self {
+        Self(row, col)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(Point(row, col): Point<T>) -> Self {
+        (row, col)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum Direction {
     NW, N, NE,
@@ -219,6 +339,125 @@ impl Direction {
             Self::E  => Self::W,
         }
     }
+
+    /// Iterate over all directions in clockwise order, starting from north.
+    pub fn all() -> impl Iterator<Item = Self> {
+        CLOCKWISE_DIRS.into_iter()
+    }
+
+    /// Iterate over the cardinal directions in clockwise order.
+    pub fn cardinals() -> impl Iterator<Item = Self> {
+        CARDINAL_DIRS.into_iter()
+    }
+
+    /// Iterate over the diagonal directions in clockwise order.
+    pub fn diagonals() -> impl Iterator<Item = Self> {
+        DIAGONAL_DIRS.into_iter()
+    }
+
+    /// Get the number of 45° clockwise turns from north.
+    fn octant(&self) -> i32 {
+        match self {
+            Self::N  => 0,
+            Self::NE => 1,
+            Self::E  => 2,
+            Self::SE => 3,
+            Self::S  => 4,
+            Self::SW => 5,
+            Self::W  => 6,
+            Self::NW => 7,
+        }
+    }
+
+    /// Turn by the given number of 45° steps (positive is counterclockwise,
+    /// like `Rot::Pos`).
+    pub fn turn(&self, steps: i32) -> Self {
+        CLOCKWISE_DIRS[(self.octant() - steps).rem_euclid(8) as usize]
+    }
+
+    pub fn rot45(&self, mag: Rot) -> Self {
+        match mag {
+            Rot::Pos => self.turn(1),
+            Rot::Neg => self.turn(-1),
+        }
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.rot90(Rot::Pos)
+    }
+
+    pub fn turn_right(&self) -> Self {
+        self.rot90(Rot::Neg)
+    }
+
+    /// Get the angle in degrees needed to turn from this direction to the
+    /// other, in the range (-180, 180] (positive is counterclockwise).
+    pub fn angle_to(&self, other: Self) -> i32 {
+        match (self.octant() - other.octant()).rem_euclid(8) {
+            n if n > 4 => 45 * (n - 8),
+            n => 45 * n,
+        }
+    }
+
+    /// Get the arrow used to display the direction.
+    pub fn arrow(&self) -> char {
+        match self {
+            Self::NW => '↖',
+            Self::N  => '↑',
+            Self::NE => '↗',
+            Self::W  => '←',
+            Self::E  => '→',
+            Self::SW => '↙',
+            Self::S  => '↓',
+            Self::SE => '↘',
+        }
+    }
+}
+
+/// Parses the common single-character direction alphabets: ASCII arrows
+/// (`^v<>`), Unicode arrows, `UDLR` and `NSEW`.
+impl TryFrom<char> for Direction {
+    type Error = ();
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '^' | '↑' | 'U' | 'N' => Ok(Self::N),
+            'v' | '↓' | 'D' | 'S' => Ok(Self::S),
+            '<' | '←' | 'L' | 'W' => Ok(Self::W),
+            '>' | '→' | 'R' | 'E' => Ok(Self::E),
+            '↖' => Ok(Self::NW),
+            '↗' => Ok(Self::NE),
+            '↙' => Ok(Self::SW),
+            '↘' => Ok(Self::SE),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Parses any single character accepted by `TryFrom<char>`, as well as the
+/// compass names `NE`, `NW`, `SE` and `SW`.
+impl FromStr for Direction {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Self::NE),
+            "NW" => Ok(Self::NW),
+            "SE" => Ok(Self::SE),
+            "SW" => Ok(Self::SW),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => Self::try_from(ch),
+                    _ => Err(()),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
 }
 
 impl<T> Add<Direction> for Point<T>
@@ -239,6 +478,47 @@ where T: PrimInt {
     }
 }
 
+impl<T> AddAssign<Direction> for Point<T>
+where T: PrimInt {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+/// Several steps in the same direction, e.g. `Point(1, 1) + E * 3`.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Steps<T>(pub Direction, pub T);
+
+impl<T> Mul<T> for Direction
+where T: PrimInt {
+    type Output = Steps<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Steps(self, rhs)
+    }
+}
+
+impl<T> Add<Steps<T>> for Point<T>
+where T: PrimInt {
+    type Output = Self;
+    fn add(self, Steps(dir, n): Steps<T>) -> Self::Output {
+        let step = |x: T, d: i8| match d {
+            -1 => x - n,
+             1 => x + n,
+             _ => x,
+        };
+
+        let Point(dr, dc) = dir.unit::<i8>();
+        Self(step(self.0, dr), step(self.1, dc))
+    }
+}
+
+impl<T> AddAssign<Steps<T>> for Point<T>
+where T: PrimInt {
+    fn add_assign(&mut self, rhs: Steps<T>) {
+        *self = *self + rhs;
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum Rot {
     Neg,
@@ -251,6 +531,475 @@ pub enum Axis {
     Horiz,
 }
 
+/// A general-purpose 3D lattice point. Order: (x, y, z)
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Point3<T>(pub T, pub T, pub T);
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Axis3 {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis3 {
+    pub const ALL: [Self; 3] = [Self::X, Self::Y, Self::Z];
+
+    /// Get the two other axes, in (x, y, z) order.
+    pub fn others(&self) -> (Self, Self) {
+        match self {
+            Self::X => (Self::Y, Self::Z),
+            Self::Y => (Self::X, Self::Z),
+            Self::Z => (Self::X, Self::Y),
+        }
+    }
+}
+
+impl<T> fmt::Display for Point3<T>
+where T: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
+    }
+}
+
+impl<T> Index<Axis3> for Point3<T> {
+    type Output = T;
+    fn index(&self, index: Axis3) -> &Self::Output {
+        match index {
+            Axis3::X => &self.0,
+            Axis3::Y => &self.1,
+            Axis3::Z => &self.2,
+        }
+    }
+}
+
+impl<T> IndexMut<Axis3> for Point3<T> {
+    fn index_mut(&mut self, index: Axis3) -> &mut Self::Output {
+        match index {
+            Axis3::X => &mut self.0,
+            Axis3::Y => &mut self.1,
+            Axis3::Z => &mut self.2,
+        }
+    }
+}
+
+impl<T> Point3<T>
+where T: PrimInt {
+    pub fn dot(self, other: Self) -> T {
+        self.0 * other.0 + self.1 * other.1 + self.2 * other.2
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        let diff = |a: T, b: T| if a > b { a - b } else { b - a };
+        diff(self.0, other.0) + diff(self.1, other.1) + diff(self.2, other.2)
+    }
+
+    /// Check whether `other` lies on the line through this point parallel to
+    /// the given axis.
+    pub fn on_axis(self, axis: Axis3, other: Self) -> bool {
+        let (a, b) = axis.others();
+        self[a] == other[a] && self[b] == other[b]
+    }
+
+    /// Get the six face-adjacent neighbors of a point
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        let one = T::one();
+        let Self(x, y, z) = self;
+        [
+            Self(x - one, y, z), Self(x + one, y, z),
+            Self(x, y - one, z), Self(x, y + one, z),
+            Self(x, y, z - one), Self(x, y, z + one),
+        ].into_iter()
+    }
+
+    /// Get all 26 neighbors of a point (including diagonally)
+    pub fn neighbors26(self) -> impl Iterator<Item = Self> {
+        let one = T::one();
+        let Self(x, y, z) = self;
+        let steps = [x - one, x, x + one];
+        steps.into_iter().flat_map(move |nx| {
+            [y - one, y, y + one].into_iter().flat_map(move |ny| {
+                [z - one, z, z + one].into_iter().map(move |nz| Self(nx, ny, nz))
+            })
+        }).filter(move |&p| p != self)
+    }
+}
+
+impl<T> Point3<T>
+where T: PrimInt + Signed {
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs(), self.1.abs(), self.2.abs())
+    }
+
+    pub fn signum(&self) -> Self {
+        Self(self.0.signum(), self.1.signum(), self.2.signum())
+    }
+
+    pub fn cross(self, other: Self) -> Self {
+        Self(
+            self.1 * other.2 - self.2 * other.1,
+            self.2 * other.0 - self.0 * other.2,
+            self.0 * other.1 - self.1 * other.0,
+        )
+    }
+}
+
+impl<T> Add for Point3<T>
+where T: PrimInt {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
+impl<T> Sub for Point3<T>
+where T: PrimInt {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
+    }
+}
+
+impl<T> Mul<T> for Point3<T>
+where T: PrimInt {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs, self.2 * rhs)
+    }
+}
+
+impl<T> Neg for Point3<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1, -self.2)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsePointError;
+
+/// Parses points written as `x,y,z` (whitespace around coordinates is ignored).
+impl<T> FromStr for Point3<T>
+where T: FromStr
+{
+    type Err = ParsePointError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').map(|part| part.trim().parse().map_err(|_| ParsePointError));
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(x), Some(y), Some(z), None) => Ok(Self(x?, y?, z?)),
+            _ => Err(ParsePointError),
+        }
+    }
+}
+
+/// An axis-aligned rectangle of lattice points with inclusive corners.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Rect<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T> Rect<T>
+where T: PrimInt {
+    /// Create the smallest rectangle containing both corners.
+    pub fn new(a: Point<T>, b: Point<T>) -> Self {
+        let min = Point(cmp::min(a.0, b.0), cmp::min(a.1, b.1));
+        let max = Point(cmp::max(a.0, b.0), cmp::max(a.1, b.1));
+        Self { min, max }
+    }
+
+    /// Get the bounding rectangle of a set of points, or `None` if there are
+    /// no points.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where I: IntoIterator<Item = Point<T>>
+    {
+        points.into_iter()
+            .map(|p| Self { min: p, max: p })
+            .reduce(|acc, rect| acc.union(&rect))
+    }
+
+    pub fn height(&self) -> T {
+        self.max.0 - self.min.0 + T::one()
+    }
+
+    pub fn width(&self) -> T {
+        self.max.1 - self.min.1 + T::one()
+    }
+
+    pub fn area(&self) -> T {
+        self.height() * self.width()
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.min.0 <= point.0 && point.0 <= self.max.0
+            && self.min.1 <= point.1 && point.1 <= self.max.1
+    }
+
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Get the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Self) -> Self {
+        let min = Point(cmp::min(self.min.0, other.min.0), cmp::min(self.min.1, other.min.1));
+        let max = Point(cmp::max(self.max.0, other.max.0), cmp::max(self.max.1, other.max.1));
+        Self { min, max }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Point(cmp::max(self.min.0, other.min.0), cmp::max(self.min.1, other.min.1));
+        let max = Point(cmp::min(self.max.0, other.max.0), cmp::min(self.max.1, other.max.1));
+        if min.0 <= max.0 && min.1 <= max.1 {
+            Some(Self { min, max })
+        } else {
+            None
+        }
+    }
+
+    /// Grow the rectangle by `n` on every side. Unsigned corners saturate at
+    /// zero rather than underflowing.
+    pub fn expand(&self, n: T) -> Self {
+        let min = Point(self.min.0.saturating_sub(n), self.min.1.saturating_sub(n));
+        let max = Point(self.max.0 + n, self.max.1 + n);
+        Self { min, max }
+    }
+
+    /// Shrink the rectangle by `n` on every side, or `None` if nothing would
+    /// be left.
+    pub fn shrink(&self, n: T) -> Option<Self> {
+        let min = Point(self.min.0 + n, self.min.1 + n);
+        let max = Point(self.max.0.checked_sub(&n)?, self.max.1.checked_sub(&n)?);
+        if min.0 <= max.0 && min.1 <= max.1 {
+            Some(Self { min, max })
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the points of the rectangle in row order.
+    pub fn points(&self) -> impl Iterator<Item = Point<T>> {
+        let Self { min, max } = *self;
+        range_inclusive(min.0, max.0)
+            .flat_map(move |row| range_inclusive(min.1, max.1).map(move |col| Point(row, col)))
+    }
+
+    /// Iterate over the points of the rectangle in column order.
+    pub fn points_by_col(&self) -> impl Iterator<Item = Point<T>> {
+        let Self { min, max } = *self;
+        range_inclusive(min.1, max.1)
+            .flat_map(move |col| range_inclusive(min.0, max.0).map(move |row| Point(row, col)))
+    }
+}
+
+fn range_inclusive<T: PrimInt>(start: T, end: T) -> impl Iterator<Item = T> {
+    let mut next = Some(start).filter(|&start| start <= end);
+    std::iter::from_fn(move || {
+        let curr = next?;
+        next = if curr < end { Some(curr + T::one()) } else { None };
+        Some(curr)
+    })
+}
+
+/// An axis-aligned box of lattice points with inclusive corners.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Cuboid<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T> Cuboid<T>
+where T: PrimInt {
+    /// Create the smallest cuboid containing both corners.
+    pub fn new(a: Point3<T>, b: Point3<T>) -> Self {
+        let min = Point3(cmp::min(a.0, b.0), cmp::min(a.1, b.1), cmp::min(a.2, b.2));
+        let max = Point3(cmp::max(a.0, b.0), cmp::max(a.1, b.1), cmp::max(a.2, b.2));
+        Self { min, max }
+    }
+
+    /// Get the number of points along an axis.
+    pub fn len(&self, axis: Axis3) -> T {
+        self.max[axis] - self.min[axis] + T::one()
+    }
+
+    pub fn volume(&self) -> T {
+        Axis3::ALL.iter().fold(T::one(), |acc, &axis| acc * self.len(axis))
+    }
+
+    pub fn contains(&self, point: Point3<T>) -> bool {
+        Axis3::ALL.iter()
+            .all(|&axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    pub fn contains_cuboid(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut min = self.min;
+        let mut max = self.max;
+        for axis in Axis3::ALL {
+            min[axis] = cmp::max(self.min[axis], other.min[axis]);
+            max[axis] = cmp::min(self.max[axis], other.max[axis]);
+            if min[axis] > max[axis] {
+                return None;
+            }
+        }
+
+        Some(Self { min, max })
+    }
+
+    /// Project the cuboid onto the plane perpendicular to the given axis.
+    /// The remaining coordinates are kept in (x, y, z) order.
+    pub fn project(&self, axis: Axis3) -> Rect<T> {
+        let (a, b) = axis.others();
+        Rect::new(Point(self.min[a], self.min[b]), Point(self.max[a], self.max[b]))
+    }
+}
+
+/// A simple lattice polygon given by its vertices in order.
+///
+/// All measurements are computed exactly in `i128`. Point tests are safe for
+/// coordinates of magnitude below 2^62. The area sums one cross product per
+/// edge, so `n` vertices with coordinates below 2^k need `n · 2^(2k+1) < 2^127`
+/// (e.g. any `i32` coordinates, or `i64` ones below 2^56 with up to 2^12
+/// vertices).
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Polygon<T> {
+    vertices: Vec<Point<T>>,
+}
+
+impl<T> Polygon<T>
+where T: PrimInt {
+    pub fn new(vertices: Vec<Point<T>>) -> Self {
+        Self { vertices }
+    }
+
+    /// Build a polygon by following a sequence of moves from a starting
+    /// point. The path is expected to return to the start.
+    pub fn from_moves<I>(start: Point<T>, moves: I) -> Self
+    where I: IntoIterator<Item = (Direction, T)>
+    {
+        let mut vertices = vec![start];
+        let mut pos = start;
+        for (dir, n) in moves {
+            pos += dir * n;
+            vertices.push(pos);
+        }
+
+        if vertices.len() > 1 && vertices.last() == Some(&start) {
+            vertices.pop();
+        }
+
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point<T>] {
+        &self.vertices
+    }
+
+    /// Get each edge as a pair of consecutive vertices, including the edge
+    /// that closes the polygon.
+    pub fn edges(&self) -> impl Iterator<Item = (Point<T>, Point<T>)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    /// Get twice the signed area (positive if the vertices run
+    /// counterclockwise when drawn with rows increasing downward).
+    pub fn double_area(&self) -> i128 {
+        self.edges()
+            .map(|(p, q)| {
+                let Point(r0, c0) = to_i128(p);
+                let Point(r1, c1) = to_i128(q);
+                r0 * c1 - r1 * c0
+            })
+            .sum()
+    }
+
+    /// Get the area enclosed by the polygon (rounded down for polygons with
+    /// half-integer area).
+    pub fn area(&self) -> i128 {
+        self.double_area().abs() / 2
+    }
+
+    /// Get the perimeter in lattice steps, i.e. the number of lattice points
+    /// on the boundary. This is the usual perimeter for polygons whose edges
+    /// are all horizontal or vertical.
+    pub fn perimeter(&self) -> i128 {
+        self.edges()
+            .map(|(p, q)| {
+                let Point(dr, dc) = to_i128(q) - to_i128(p);
+                crate::math::gcd(dr.abs(), dc.abs())
+            })
+            .sum()
+    }
+
+    /// Count the lattice points strictly inside the polygon (Pick's theorem).
+    /// Degenerate polygons with no area have no interior.
+    pub fn interior_points(&self) -> i128 {
+        match self.double_area().abs() {
+            0 => 0,
+            double_area => (double_area - self.perimeter() + 2) / 2,
+        }
+    }
+
+    /// Count the lattice points inside or on the boundary of the polygon,
+    /// i.e. the number of cells covered by a trench dug along its edges.
+    pub fn lattice_points(&self) -> i128 {
+        self.interior_points() + self.perimeter()
+    }
+
+    pub fn on_boundary(&self, point: Point<T>) -> bool {
+        let p = to_i128(point);
+        self.edges().any(|(a, b)| {
+            let (a, b) = (to_i128(a), to_i128(b));
+            let Point(dr, dc) = b - a;
+            let Point(pr, pc) = p - a;
+            dr * pc - dc * pr == 0
+                && cmp::min(a.0, b.0) <= p.0 && p.0 <= cmp::max(a.0, b.0)
+                && cmp::min(a.1, b.1) <= p.1 && p.1 <= cmp::max(a.1, b.1)
+        })
+    }
+
+    /// Check whether a point is inside the polygon or on its boundary.
+    pub fn contains(&self, point: Point<T>) -> bool {
+        if self.on_boundary(point) {
+            return true;
+        }
+
+        let Point(pr, pc) = to_i128(point);
+        let crossings = self.edges()
+            .filter(|&(a, b)| {
+                let (Point(ar, ac), Point(br, bc)) = (to_i128(a), to_i128(b));
+                if (ar > pr) == (br > pr) {
+                    return false;
+                }
+
+                // is the point left of where the edge crosses its row?
+                let lhs = (pc - ac) * (br - ar);
+                let rhs = (pr - ar) * (bc - ac);
+                if br > ar { lhs < rhs } else { lhs > rhs }
+            })
+            .count();
+
+        crossings % 2 == 1
+    }
+}
+
+fn to_i128<T: PrimInt>(p: Point<T>) -> Point<i128> {
+    Point(p.0.to_i128().unwrap(), p.1.to_i128().unwrap())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,6 +1032,121 @@ mod tests {
         assert_eq!(Point(-1_i32, 1_i32), Point(0_i32, 0_i32) - Point(1_i32, -1_i32));
     }
 
+    #[test]
+    fn scalar() {
+        assert_eq!(Point(6_u32, 9_u32), Point(2_u32, 3_u32) * 3);
+        assert_eq!(Point(-2_i32, 1_i32), Point(-5_i32, 3_i32) / 2);
+        assert_eq!(Point(-2_i32, 3_i32), -Point(2_i32, -3_i32));
+
+        let mut p = Point(1_u32, 1_u32);
+        p += Point(2, 3);
+        p -= Point(1, 0);
+        p += Direction::SE;
+        assert_eq!(Point(3, 5), p);
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(Point(1_usize, 5_usize), Point(1_usize, 2_usize) + Direction::E * 3);
+        assert_eq!(Point(-3_i64, 4_i64), Point(0_i64, 7_i64) + Direction::NW * 3);
+
+        let mut p = Point(5_usize, 5_usize);
+        p += Direction::S * 2;
+        assert_eq!(Point(7, 5), p);
+    }
+
+    #[test]
+    fn distances() {
+        let p = Point(1_usize, 7_usize);
+        let q = Point(4_usize, 2_usize);
+        assert_eq!(8, p.manhattan(q));
+        assert_eq!(5, p.chebyshev(q));
+        assert_eq!(Point(3, 5), p.abs_diff(q));
+    }
+
+    #[test]
+    fn checked() {
+        let origin = Point(0_usize, 0_usize);
+        assert_eq!(None, origin.checked_step(Direction::N));
+        assert_eq!(None, origin.checked_step(Direction::SW));
+        assert_eq!(Some(Point(1, 1)), origin.checked_step(Direction::SE));
+        assert_eq!(None, Point(2_usize, 5_usize).checked_steps(Direction::NE, 3));
+        assert_eq!(None, Point(0_u8, 255_u8).checked_step(Direction::E));
+
+        assert_eq!(Some(Point(3_usize, 4_usize)), Point(3_isize, 4_isize).cast());
+        assert_eq!(None, Point(-1_isize, 4_isize).cast::<usize>());
+        assert_eq!(Some(Point(3_i64, 4_i64)), Point(3_usize, 4_usize).cast());
+    }
+
+    #[test]
+    fn tuples() {
+        assert_eq!(Point(1, 2), (1, 2).into());
+        assert_eq!((1, 2), Point(1, 2).into());
+    }
+
+    #[test]
+    fn rot90() {
+        for dir in NEIGHBORS {
+            for rot in [Rot::Pos, Rot::Neg] {
+                assert_eq!(dir.rot90(rot).unit::<i32>(), dir.unit::<i32>().rot90(rot));
+            }
+        }
+        assert_eq!(Point(-5, 2), Point(2, 5).rot90(Rot::Pos));
+    }
+
+    #[test]
+    fn direction_parse() {
+        assert_eq!(Ok(Direction::N), Direction::try_from('^'));
+        assert_eq!(Ok(Direction::S), Direction::try_from('D'));
+        assert_eq!(Ok(Direction::W), Direction::try_from('W'));
+        assert_eq!(Ok(Direction::E), Direction::try_from('>'));
+        assert_eq!(Err(()), Direction::try_from('x'));
+
+        assert_eq!(Ok(Direction::SW), "SW".parse());
+        assert_eq!(Ok(Direction::N), "U".parse());
+        assert_eq!(Err(()), "UD".parse::<Direction>());
+
+        for dir in Direction::all() {
+            assert_eq!(Ok(dir), dir.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn direction_iter() {
+        assert_eq!(8, Direction::all().count());
+        assert!(Direction::cardinals().all(|dir| dir.cardinal()));
+        assert!(Direction::diagonals().all(|dir| !dir.cardinal()));
+    }
+
+    #[test]
+    fn direction_turn() {
+        use Direction::*;
+
+        for dir in Direction::all() {
+            assert_eq!(dir.rot90(Rot::Pos), dir.rot45(Rot::Pos).rot45(Rot::Pos));
+            assert_eq!(dir.rot90(Rot::Neg), dir.turn(-2));
+            assert_eq!(dir.opp(), dir.turn(4));
+            assert_eq!(dir, dir.turn_left().turn_right());
+        }
+
+        assert_eq!(NW, N.rot45(Rot::Pos));
+        assert_eq!(S, E.turn_right());
+        assert_eq!(W, S.turn(-10));
+    }
+
+    #[test]
+    fn direction_angle() {
+        use Direction::*;
+
+        assert_eq!(0, N.angle_to(N));
+        assert_eq!(90, N.angle_to(W));
+        assert_eq!(-45, N.angle_to(NE));
+        assert_eq!(180, E.angle_to(W));
+        assert_eq!(180, W.angle_to(E));
+        assert_eq!(135, SW.angle_to(E));
+        assert_eq!(-135, E.angle_to(SW));
+    }
+
     #[test]
     fn neighbors() {
         let origin = Point(0_i32, 0_i32);
@@ -309,6 +1173,163 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn point3_arith() {
+        let p = Point3(1_i32, 2, 3);
+        let q = Point3(4_i32, -5, 6);
+
+        assert_eq!(Point3(5, -3, 9), p + q);
+        assert_eq!(Point3(-3, 7, -3), p - q);
+        assert_eq!(Point3(-2, -4, -6), -p * 2);
+        assert_eq!(12, p.dot(q));
+        assert_eq!(Point3(27, 6, -13), p.cross(q));
+        assert_eq!(0, p.cross(q).dot(p));
+        assert_eq!(13, p.manhattan(q));
+        assert_eq!(6, Point3(5_u32, 0, 2).manhattan(Point3(2, 1, 4)));
+    }
+
+    #[test]
+    fn point3_neighbors() {
+        let origin = Point3(1_u32, 1, 1);
+
+        let ns: HashSet<_> = origin.neighbors6().collect();
+        assert_eq!(6, ns.len());
+        assert!(ns.iter().all(|&n| n.manhattan(origin) == 1));
+
+        let ns: HashSet<_> = origin.neighbors26().collect();
+        assert_eq!(26, ns.len());
+        assert!(!ns.contains(&origin));
+        assert!(ns.contains(&Point3(0, 2, 0)));
+    }
+
+    #[test]
+    fn point3_parse() {
+        assert_eq!(Ok(Point3(19, 13, -30)), "19, 13, -30".parse());
+        assert_eq!(Err(ParsePointError), "1,2".parse::<Point3<i32>>());
+        assert_eq!(Err(ParsePointError), "1,2,3,4".parse::<Point3<i32>>());
+    }
+
+    #[test]
+    fn cuboid() {
+        let a = Cuboid::new(Point3(0_u32, 0, 0), Point3(2, 2, 2));
+        let b = Cuboid::new(Point3(3_u32, 1, 1), Point3(1, 4, 1));
+
+        assert_eq!(27, a.volume());
+        assert_eq!(12, b.volume());
+        assert_eq!(Some(Cuboid::new(Point3(1, 1, 1), Point3(2, 2, 1))), a.intersection(&b));
+        assert!(a.contains(Point3(2, 0, 1)));
+        assert!(!a.contains(Point3(3, 0, 1)));
+        assert!(a.contains_cuboid(&Cuboid::new(Point3(1, 1, 1), Point3(2, 2, 2))));
+        assert!(!a.contains_cuboid(&b));
+        assert!(!a.overlaps(&Cuboid::new(Point3(0, 0, 3), Point3(0, 0, 5))));
+        assert_eq!(Rect::new(Point(1, 1), Point(3, 4)), b.project(Axis3::Z));
+    }
+
+    #[test]
+    fn rect_from_points() {
+        let rect = Rect::from_points([Point(3_i32, -1), Point(0, 2), Point(1, 1)]).unwrap();
+        assert_eq!(Rect { min: Point(0, -1), max: Point(3, 2) }, rect);
+        assert_eq!(4, rect.height());
+        assert_eq!(4, rect.width());
+        assert_eq!(16, rect.area());
+        assert!(rect.contains(Point(3, -1)));
+        assert!(!rect.contains(Point(4, 0)));
+
+        assert_eq!(None, Rect::<i32>::from_points([]));
+    }
+
+    #[test]
+    fn rect_set_ops() {
+        let a = Rect::new(Point(0_u32, 0), Point(3, 3));
+        let b = Rect::new(Point(2_u32, 1), Point(5, 2));
+
+        assert_eq!(Rect::new(Point(0, 0), Point(5, 3)), a.union(&b));
+        assert_eq!(Some(Rect::new(Point(2, 1), Point(3, 2))), a.intersection(&b));
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&Rect::new(Point(4, 4), Point(4, 4))));
+        assert!(a.contains_rect(&Rect::new(Point(1, 1), Point(2, 3))));
+        assert!(!a.contains_rect(&b));
+    }
+
+    #[test]
+    fn rect_resize() {
+        let rect = Rect::new(Point(1_usize, 2), Point(4, 4));
+        assert_eq!(Rect::new(Point(0, 0), Point(6, 6)), rect.expand(2));
+        assert_eq!(Some(Rect::new(Point(2, 3), Point(3, 3))), rect.shrink(1));
+        assert_eq!(None, rect.shrink(2));
+    }
+
+    #[test]
+    fn rect_points() {
+        let rect = Rect::new(Point(1_u8, 254), Point(2, 255));
+
+        let rows: Vec<_> = rect.points().collect();
+        assert_eq!(vec![Point(1, 254), Point(1, 255), Point(2, 254), Point(2, 255)], rows);
+
+        let cols: Vec<_> = rect.points_by_col().collect();
+        assert_eq!(vec![Point(1, 254), Point(2, 254), Point(1, 255), Point(2, 255)], cols);
+    }
+
+    #[test]
+    fn polygon_measure() {
+        use Direction::*;
+
+        // the example trench from 2023 day 18
+        let moves = [
+            (E, 6), (S, 5), (W, 2), (S, 2), (E, 2), (S, 2), (W, 5),
+            (N, 2), (W, 1), (N, 2), (E, 2), (N, 3), (W, 2), (N, 2),
+        ];
+        let poly = Polygon::from_moves(Point(0_i64, 0), moves);
+
+        assert_eq!(14, poly.vertices().len());
+        assert_eq!(38, poly.perimeter());
+        assert_eq!(42, poly.area());
+        assert_eq!(24, poly.interior_points());
+        assert_eq!(62, poly.lattice_points());
+    }
+
+    #[test]
+    fn polygon_diagonal() {
+        let poly = Polygon::new(vec![Point(0_i32, 0), Point(3, 3), Point(0, 6)]);
+
+        assert_eq!(18, poly.double_area());
+        assert_eq!(9, poly.area());
+        assert_eq!(12, poly.perimeter());
+        assert_eq!(4, poly.interior_points());
+    }
+
+    #[test]
+    fn polygon_contains() {
+        let poly = Polygon::new(vec![
+            Point(0_i64, 0), Point(0, 4), Point(4, 4), Point(4, 2), Point(2, 2), Point(2, 0),
+        ]);
+
+        assert!(poly.contains(Point(1, 1)));
+        assert!(poly.contains(Point(3, 3)));
+        assert!(!poly.contains(Point(3, 1)));
+        assert!(!poly.contains(Point(-1, 2)));
+        assert!(poly.contains(Point(2, 1)));
+        assert!(poly.on_boundary(Point(2, 1)));
+        assert!(!poly.on_boundary(Point(1, 1)));
+
+        let m = 1_i64 << 56;
+        let big = Polygon::new(vec![Point(-m, -m), Point(-m, m), Point(m, m), Point(m, -m)]);
+        assert!(big.contains(Point(m / 2, 1 - m)));
+        assert!(!big.contains(Point(m / 2, -1 - m)));
+        assert_eq!(4 * (m as i128).pow(2), big.area());
+    }
+
+    #[test]
+    fn polygon_degenerate() {
+        let empty = Polygon::<i64>::new(vec![]);
+        let segment = Polygon::new(vec![Point(0_i64, 0), Point(0, 4)]);
+        let collinear = Polygon::new(vec![Point(0_i64, 0), Point(2, 2), Point(5, 5)]);
+        for poly in [empty, segment, collinear] {
+            assert_eq!(0, poly.area());
+            assert_eq!(0, poly.interior_points());
+        }
+    }
+
     #[test]
     fn ortho_to() {
         let origin = Point(0_i32, 0_i32);
@@ -0,0 +1,154 @@
+//! Iteration over balls and rings of lattice points under the Manhattan
+//! (diamond) and Chebyshev (square) metrics.
+//!
+//! Points that can't be represented in the coordinate type (e.g. negative
+//! rows around an `IdxPoint`) are skipped.
+
+use std::cmp;
+
+use num_traits::PrimInt;
+
+use super::{to_i128, Point, Rect};
+
+impl<T> Point<T>
+where T: PrimInt {
+    /// Get all points at exactly Manhattan distance `k`, clockwise from the
+    /// northmost point.
+    pub fn diamond_ring(self, k: T) -> impl Iterator<Item = Self> {
+        let k = k.to_i128().unwrap();
+        let sides = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+        let offsets = sides.into_iter().enumerate().flat_map(move |(side, (sr, sc))| {
+            // start each side at a corner: N, E, S, W
+            let corner = match side {
+                0 => (-k, 0),
+                1 => (0, k),
+                2 => (k, 0),
+                _ => (0, -k),
+            };
+            (0..k).map(move |i| (corner.0 + sr * i, corner.1 + sc * i))
+        });
+
+        let offsets: Box<dyn Iterator<Item = (i128, i128)>> = if k == 0 {
+            Box::new(std::iter::once((0, 0)))
+        } else {
+            Box::new(offsets)
+        };
+
+        offsets.filter_map(move |(dr, dc)| offset(self, dr, dc))
+    }
+
+    /// Get all points within Manhattan distance `k`, in row order.
+    pub fn diamond(self, k: T) -> impl Iterator<Item = Self> {
+        let k = k.to_i128().unwrap();
+        (-k..=k).flat_map(move |dr| {
+            let w = k - dr.abs();
+            (-w..=w).filter_map(move |dc| offset(self, dr, dc))
+        })
+    }
+
+    /// Get all points at exactly Chebyshev distance `k`, clockwise from the
+    /// northwest corner.
+    pub fn square_ring(self, k: T) -> impl Iterator<Item = Self> {
+        let k = k.to_i128().unwrap();
+        let top = (-k..k).map(move |dc| (-k, dc));
+        let right = (-k..k).map(move |dr| (dr, k));
+        let bottom = (-k..k).map(move |dc| (k, -dc));
+        let left = (-k..k).map(move |dr| (-dr, -k));
+
+        let offsets: Box<dyn Iterator<Item = (i128, i128)>> = if k == 0 {
+            Box::new(std::iter::once((0, 0)))
+        } else {
+            Box::new(top.chain(right).chain(bottom).chain(left))
+        };
+
+        offsets.filter_map(move |(dr, dc)| offset(self, dr, dc))
+    }
+
+    /// Get all points within Chebyshev distance `k`, in row order.
+    pub fn square(self, k: T) -> impl Iterator<Item = Self> {
+        let k = k.to_i128().unwrap();
+        (-k..=k).flat_map(move |dr| (-k..=k).filter_map(move |dc| offset(self, dr, dc)))
+    }
+}
+
+impl<T> Rect<T>
+where T: PrimInt {
+    /// Count the points of the rectangle within Manhattan distance `k` of
+    /// `center`, without visiting them individually.
+    pub fn diamond_count(&self, center: Point<T>, k: T) -> T {
+        let Point(cr, cc) = to_i128(center);
+        let (min, max) = (to_i128(self.min), to_i128(self.max));
+        let k = k.to_i128().unwrap();
+
+        let rows = cmp::max(min.0, cr - k)..=cmp::min(max.0, cr + k);
+        let count: i128 = rows
+            .map(|row| {
+                let w = k - (row - cr).abs();
+                let lo = cmp::max(min.1, cc - w);
+                let hi = cmp::min(max.1, cc + w);
+                cmp::max(hi - lo + 1, 0)
+            })
+            .sum();
+
+        T::from(count).unwrap()
+    }
+}
+
+fn offset<T: PrimInt>(p: Point<T>, dr: i128, dc: i128) -> Option<Point<T>> {
+    let Point(r, c) = to_i128(p);
+    Some(Point(T::from(r + dr)?, T::from(c + dc)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn diamond_ring() {
+        let center = Point(0_i32, 0);
+        for k in 0..5 {
+            let ring: Vec<_> = center.diamond_ring(k).collect();
+            let uniq: HashSet<_> = ring.iter().copied().collect();
+            assert_eq!(cmp::max(4 * k as usize, 1), ring.len());
+            assert_eq!(ring.len(), uniq.len());
+            assert!(ring.iter().all(|&p| p.manhattan(center) == k));
+        }
+
+        let ring: Vec<_> = Point(0_usize, 1).diamond_ring(1).collect();
+        assert_eq!(vec![Point(0, 2), Point(1, 1), Point(0, 0)], ring);
+    }
+
+    #[test]
+    fn diamond() {
+        let center = Point(5_i64, -3);
+        let ball: HashSet<_> = center.diamond(3).collect();
+        let rings: HashSet<_> = (0..=3).flat_map(|k| center.diamond_ring(k)).collect();
+        assert_eq!(25, ball.len());
+        assert_eq!(rings, ball);
+    }
+
+    #[test]
+    fn square() {
+        let center = Point(0_i32, 0);
+        let ring: Vec<_> = center.square_ring(2).collect();
+        assert_eq!(16, ring.len());
+        assert!(ring.iter().all(|&p| p.chebyshev(center) == 2));
+
+        let ball: HashSet<_> = center.square(2).collect();
+        assert_eq!(25, ball.len());
+
+        assert_eq!(4, Point(0_u32, 0).square(1).count());
+    }
+
+    #[test]
+    fn diamond_count() {
+        let rect = Rect::new(Point(1_usize, 1), Point(7, 10));
+        for center in [Point(1, 1), Point(4, 5), Point(7, 10), Point(9, 0)] {
+            for k in [0, 1, 3, 6, 20] {
+                let naive = center.diamond(k).filter(|&p| rect.contains(p)).count();
+                assert_eq!(naive, rect.diamond_count(center, k));
+            }
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Hexagonal lattices in axial coordinates.
+//!
+//! Hexes are flat-topped: each hex has neighbors to the north and south and
+//! on the four diagonals, but none directly east or west. The third cube
+//! coordinate is implicit, with `q + r + s == 0`.
+
+use std::ops::{Add, AddAssign, Sub, Mul, Neg};
+use std::fmt;
+use std::str::FromStr;
+
+use num_traits::{PrimInt, Signed};
+
+use super::{Point, Rot};
+
+/// All hex directions in clockwise order, starting from north.
+pub static HEX_DIRS: [HexDir; 6] = [
+    HexDir::N, HexDir::NE, HexDir::SE, HexDir::S, HexDir::SW, HexDir::NW,
+];
+
+/// A hex in axial coordinates. Order: (q, r)
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Hex<T>(pub T, pub T);
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum HexDir {
+    N, NE, SE, S, SW, NW,
+}
+
+impl<T> Hex<T>
+where T: PrimInt + Signed {
+    /// Get the implicit third cube coordinate.
+    pub fn s(&self) -> T {
+        -self.0 - self.1
+    }
+
+    pub fn step(self, dir: HexDir) -> Self {
+        self + dir.unit()
+    }
+
+    /// Get the six neighbors of this hex, clockwise from north.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        HEX_DIRS.iter().map(move |&dir| self.step(dir))
+    }
+
+    /// Get the number of steps between two hexes.
+    pub fn distance(self, other: Self) -> T {
+        let diff = self - other;
+        diff.0.abs().max(diff.1.abs()).max(diff.s().abs())
+    }
+
+    /// Get all hexes at exactly `k` steps from this one, clockwise from the
+    /// northmost hex.
+    pub fn ring(self, k: T) -> impl Iterator<Item = Self> {
+        let n = k.to_usize().unwrap();
+        let start = self + HexDir::N.unit() * k;
+        let sides = HEX_DIRS.iter().cycle().skip(2).take(6);
+        let mut hexes: Vec<_> = sides
+            .scan(start, move |curr, &dir| {
+                let side: Vec<_> = (0..n).map(|_| {
+                    let hex = *curr;
+                    *curr = curr.step(dir);
+                    hex
+                }).collect();
+                Some(side)
+            })
+            .flatten()
+            .collect();
+
+        if n == 0 {
+            hexes.push(self);
+        }
+        hexes.into_iter()
+    }
+
+    /// Get all hexes within `k` steps of this one, ring by ring.
+    pub fn disc(self, k: T) -> impl Iterator<Item = Self> {
+        let n = k.to_i64().unwrap();
+        (0..=n).flat_map(move |i| self.ring(T::from(i).unwrap()))
+    }
+
+    /// Rotate a sixth of a turn about the origin. Matches `HexDir::rot60`.
+    pub fn rot60(self, rot: Rot) -> Self {
+        match rot {
+            Rot::Pos => Self(-self.s(), -self.0),
+            Rot::Neg => Self(-self.1, -self.s()),
+        }
+    }
+
+    /// Convert to (row, col) offset coordinates, with odd columns shifted
+    /// down half a row. The origin maps to (0, 0).
+    pub fn to_offset(self) -> Point<T> {
+        let one = T::one();
+        let two = one + one;
+        let q = self.0;
+        Point(self.1 + (q - (q & one)) / two, q)
+    }
+
+    /// Convert from (row, col) offset coordinates; inverse of `to_offset`.
+    pub fn from_offset(Point(row, col): Point<T>) -> Self {
+        let one = T::one();
+        let two = one + one;
+        Self(col, row - (col - (col & one)) / two)
+    }
+
+    /// Get the 1-based grid index (an `IdxPoint`) of the hex in a map whose
+    /// top left cell at (1, 1) holds `origin`, or `None` if the hex lies above
+    /// or left of the map.
+    pub fn to_idx(self, origin: Self) -> Option<Point<usize>> {
+        let Point(row, col) = (self.to_offset() - origin.to_offset()).cast::<usize>()?;
+        Some(Point(row.checked_add(1)?, col.checked_add(1)?))
+    }
+
+    /// Get the hex stored at a 1-based grid index in a map whose top left
+    /// cell holds `origin`; inverse of `to_idx`.
+    pub fn from_idx(Point(row, col): Point<usize>, origin: Self) -> Option<Self> {
+        let shift = Point(row.checked_sub(1)?, col.checked_sub(1)?).cast::<T>()?;
+        Some(Self::from_offset(origin.to_offset() + shift))
+    }
+}
+
+impl HexDir {
+    pub fn unit<T: PrimInt + Signed>(&self) -> Hex<T> {
+        let zero = T::zero();
+        let one = T::one();
+        match *self {
+            Self::N  => Hex(zero, -one),
+            Self::NE => Hex(one, -one),
+            Self::SE => Hex(one, zero),
+            Self::S  => Hex(zero, one),
+            Self::SW => Hex(-one, one),
+            Self::NW => Hex(-one, zero),
+        }
+    }
+
+    /// Turn by the given number of 60° steps (positive is counterclockwise,
+    /// like `Rot::Pos`).
+    pub fn turn(&self, steps: i32) -> Self {
+        let idx = HEX_DIRS.iter().position(|dir| dir == self).unwrap() as i32;
+        HEX_DIRS[(idx - steps).rem_euclid(6) as usize]
+    }
+
+    pub fn rot60(&self, rot: Rot) -> Self {
+        match rot {
+            Rot::Pos => self.turn(1),
+            Rot::Neg => self.turn(-1),
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn(3)
+    }
+
+    /// Parse a comma-separated list of directions, e.g. `ne,sw,nw`.
+    pub fn parse_path(s: &str) -> Option<Vec<Self>> {
+        s.trim().split(',').map(|dir| dir.trim().parse().ok()).collect()
+    }
+}
+
+impl FromStr for HexDir {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "n" => Ok(Self::N),
+            "ne" => Ok(Self::NE),
+            "se" => Ok(Self::SE),
+            "s" => Ok(Self::S),
+            "sw" => Ok(Self::SW),
+            "nw" => Ok(Self::NW),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for HexDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            Self::N => "n",
+            Self::NE => "ne",
+            Self::SE => "se",
+            Self::S => "s",
+            Self::SW => "sw",
+            Self::NW => "nw",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl<T> fmt::Display for Hex<T>
+where T: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}, {}>", self.0, self.1)
+    }
+}
+
+impl<T> Add for Hex<T>
+where T: PrimInt {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl<T> Sub for Hex<T>
+where T: PrimInt {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self::Output {
+        Self(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl<T> Mul<T> for Hex<T>
+where T: PrimInt {
+    type Output = Self;
+    fn mul(self, k: T) -> Self::Output {
+        Self(self.0 * k, self.1 * k)
+    }
+}
+
+impl<T> Neg for Hex<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl<T> Add<HexDir> for Hex<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn add(self, dir: HexDir) -> Self::Output {
+        self.step(dir)
+    }
+}
+
+impl<T> AddAssign<HexDir> for Hex<T>
+where T: PrimInt + Signed {
+    fn add_assign(&mut self, dir: HexDir) {
+        *self = self.step(dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn walk(path: &str) -> Hex<i32> {
+        HexDir::parse_path(path).unwrap().into_iter().fold(Hex(0, 0), |hex, dir| hex + dir)
+    }
+
+    #[test]
+    fn parse_and_distance() {
+        let origin = Hex(0, 0);
+        assert_eq!(3, walk("ne,ne,ne").distance(origin));
+        assert_eq!(0, walk("ne,ne,sw,sw").distance(origin));
+        assert_eq!(2, walk("ne,ne,s,s").distance(origin));
+        assert_eq!(3, walk("se,sw,se,sw,sw").distance(origin));
+        assert_eq!(None, HexDir::parse_path("n,e"));
+    }
+
+    #[test]
+    fn neighbors() {
+        let hex = Hex(2, -1);
+        assert!(hex.neighbors().all(|n| n.distance(hex) == 1));
+        for dir in HEX_DIRS {
+            assert_eq!(hex, hex + dir + dir.opposite());
+        }
+    }
+
+    #[test]
+    fn rings() {
+        let center = Hex(1, 1);
+        assert_eq!(vec![center], center.ring(0).collect::<Vec<_>>());
+        for k in 1..5 {
+            let ring: HashSet<_> = center.ring(k).collect();
+            assert_eq!(6 * k as usize, ring.len());
+            assert!(ring.iter().all(|&hex| hex.distance(center) == k));
+        }
+        assert_eq!(37, center.disc(3).count());
+    }
+
+    #[test]
+    fn rotation() {
+        for dir in HEX_DIRS {
+            for rot in [Rot::Pos, Rot::Neg] {
+                assert_eq!(dir.rot60(rot).unit::<i32>(), dir.unit::<i32>().rot60(rot));
+            }
+        }
+        assert_eq!(HexDir::NW, HexDir::N.rot60(Rot::Pos));
+        assert_eq!(Hex(3, -1), Hex(3, -1).rot60(Rot::Pos).rot60(Rot::Neg));
+    }
+
+    #[test]
+    fn offset() {
+        for hex in Hex(0, 0).disc(4) {
+            assert_eq!(hex, Hex::from_offset(hex.to_offset()));
+        }
+        assert_eq!(Point(0, 1), Hex(1, 0).to_offset());
+        assert_eq!(Point(0, 2), Hex(2, -1).to_offset());
+        assert_eq!(Point(-1, -1), Hex(-1, 0).to_offset());
+    }
+
+    #[test]
+    fn grid_index() {
+        use crate::grid::Vec2D;
+
+        let center = Hex(2_i32, -5);
+        let origin = Hex::from_offset(center.to_offset() - Point(3, 3));
+        let mut map = Vec2D::from_rows(vec![vec![None; 7]; 7]);
+        for hex in center.disc(3) {
+            let idx = hex.to_idx(origin).unwrap();
+            assert_eq!(None, map[idx]);
+            map[idx] = Some(hex);
+            assert_eq!(Some(hex), Hex::from_idx(idx, origin));
+        }
+        assert_eq!(Some(center), map[Point(4, 4)]);
+        assert_eq!(Some(Point(1, 1)), origin.to_idx(origin));
+
+        assert_eq!(None, (origin + HexDir::N.unit()).to_idx(origin));
+        assert_eq!(None, (origin + HexDir::SW.unit()).to_idx(origin));
+        assert_eq!(None, Hex::from_idx(Point(0, 3), origin));
+    }
+}
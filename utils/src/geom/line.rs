@@ -0,0 +1,418 @@
+//! Lines, rays and segments over integer coordinates with exact
+//! intersection tests.
+//!
+//! Everything is computed exactly with `i128` cross products. Intersection
+//! points are products of a coordinate and two directions, so inputs with
+//! |coord| < 2^a and |dir| < 2^b are safe when `a + 2b <= 124`, e.g.
+//! positions up to 2^60 moving at up to 2^32 per step. A segment's direction
+//! is the difference of its endpoints, so segment endpoints must stay below
+//! 2^40. Larger inputs may overflow.
+
+use std::cmp::{self, Ordering};
+use std::fmt;
+
+use num_traits::{PrimInt, Signed};
+
+use super::{to_i128, Point, Rect};
+use crate::math::gcd;
+
+/// A point with rational coordinates, stored as numerators over a shared
+/// positive denominator in lowest terms.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct RatPoint {
+    pub num: Point<i128>,
+    pub den: i128,
+}
+
+impl RatPoint {
+    pub fn new(num: Point<i128>, den: i128) -> Self {
+        if den == 0 {
+            panic!("zero denominator in rational point");
+        }
+
+        let sign = den.signum();
+        let g = gcd(gcd(num.0.abs(), num.1.abs()), den.abs());
+        Self { num: Point(sign * num.0 / g, sign * num.1 / g), den: den.abs() / g }
+    }
+
+    /// Get the point if both coordinates are integers.
+    pub fn to_point(&self) -> Option<Point<i128>> {
+        if self.den == 1 {
+            Some(self.num)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> (f64, f64) {
+        let den = self.den as f64;
+        (self.num.0 as f64 / den, self.num.1 as f64 / den)
+    }
+
+    /// Check whether the point lies inside (or on the edge of) a rectangle.
+    pub fn within<T: PrimInt>(&self, rect: &Rect<T>) -> bool {
+        let min = to_i128(rect.min);
+        let max = to_i128(rect.max);
+        min.0 * self.den <= self.num.0 && self.num.0 <= max.0 * self.den
+            && min.1 * self.den <= self.num.1 && self.num.1 <= max.1 * self.den
+    }
+}
+
+impl From<Point<i128>> for RatPoint {
+    fn from(num: Point<i128>) -> Self {
+        Self { num, den: 1 }
+    }
+}
+
+impl fmt::Display for RatPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "({}/{}, {}/{})", self.num.0, self.den, self.num.1, self.den)
+        }
+    }
+}
+
+/// How two infinite lines `p + t·r` and `q + u·s` relate.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Crossing {
+    Parallel,
+    Collinear,
+    /// The lines cross where `t = t_num / den` and `u = u_num / den`.
+    At { t_num: i128, u_num: i128, den: i128 },
+}
+
+impl Crossing {
+    /// Check whether the crossing happens at non-negative parameters on both
+    /// lines, i.e. in the future for two moving objects.
+    pub fn in_future(&self) -> bool {
+        match *self {
+            Self::At { t_num, u_num, .. } => t_num >= 0 && u_num >= 0,
+            _ => false,
+        }
+    }
+}
+
+/// The intersection of two linear objects.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Intersection {
+    None,
+    Point(RatPoint),
+    /// The objects are collinear and share more than one point.
+    Overlap,
+}
+
+/// An infinite line through `origin` along `dir`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Line<T> {
+    pub origin: Point<T>,
+    pub dir: Point<T>,
+}
+
+/// A ray starting at `origin` and heading along `dir`, e.g. the path of an
+/// object moving with constant velocity.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Ray<T> {
+    pub origin: Point<T>,
+    pub dir: Point<T>,
+}
+
+/// A closed segment between two points.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Segment<T> {
+    pub start: Point<T>,
+    pub end: Point<T>,
+}
+
+impl<T> Line<T>
+where T: PrimInt + Signed {
+    pub fn through(a: Point<T>, b: Point<T>) -> Self {
+        Self { origin: a, dir: b - a }
+    }
+}
+
+/// Common behavior of lines, rays and segments, which are all described by
+/// a parametric line `origin + t·dir` restricted to some range of `t`.
+pub trait Linear<T: PrimInt> {
+    fn origin(&self) -> Point<T>;
+    fn dir(&self) -> Point<T>;
+
+    /// Check whether the parameter `t = num / den` (with `den > 0`) lies on
+    /// the object.
+    fn has_param(&self, num: i128, den: i128) -> bool;
+
+    /// Get the range of coordinates the object covers along one axis
+    /// (0 for rows, 1 for columns), or `None` for an unbounded end.
+    fn extent(&self, axis: usize) -> (Option<i128>, Option<i128>);
+
+    /// Classify how the infinite lines through both objects meet.
+    fn crossing<L: Linear<T>>(&self, other: &L) -> Crossing {
+        let p = to_i128(self.origin());
+        let r = to_i128(self.dir());
+        let q = to_i128(other.origin());
+        let s = to_i128(other.dir());
+
+        let den = cross(r, s);
+        let qp = q - p;
+        if den == 0 {
+            if cross(qp, r) == 0 {
+                Crossing::Collinear
+            } else {
+                Crossing::Parallel
+            }
+        } else {
+            let (t_num, u_num) = (cross(qp, s), cross(qp, r));
+            if den < 0 {
+                Crossing::At { t_num: -t_num, u_num: -u_num, den: -den }
+            } else {
+                Crossing::At { t_num, u_num, den }
+            }
+        }
+    }
+
+    fn intersection<L: Linear<T>>(&self, other: &L) -> Intersection {
+        match self.crossing(other) {
+            Crossing::Parallel => Intersection::None,
+            Crossing::At { t_num, u_num, den } => {
+                if self.has_param(t_num, den) && other.has_param(u_num, den) {
+                    Intersection::Point(point_at(self, t_num, den))
+                } else {
+                    Intersection::None
+                }
+            }
+            // a zero direction always looks collinear, so check the point
+            // against the other object directly
+            Crossing::Collinear if is_point(self) =>
+                meets_point(other, to_i128(self.origin())),
+            Crossing::Collinear if is_point(other) =>
+                meets_point(self, to_i128(other.origin())),
+            Crossing::Collinear => {
+                // compare positions along whichever axis the line isn't
+                // perpendicular to, where everything is an integer
+                let axis = if self.dir().0.is_zero() { 1 } else { 0 };
+                let (lo1, hi1) = self.extent(axis);
+                let (lo2, hi2) = other.extent(axis);
+
+                let lo = cmp::max(lo1, lo2);
+                let hi = match (hi1, hi2) {
+                    (Some(a), Some(b)) => Some(cmp::min(a, b)),
+                    (a, b) => a.or(b),
+                };
+
+                match (lo, hi) {
+                    (Some(lo), Some(hi)) => match lo.cmp(&hi) {
+                        Ordering::Less => Intersection::Overlap,
+                        Ordering::Greater => Intersection::None,
+                        Ordering::Equal => Intersection::Point(
+                            collinear_point(self, axis, lo).into()),
+                    },
+                    _ => Intersection::Overlap,
+                }
+            }
+        }
+    }
+
+    fn intersects<L: Linear<T>>(&self, other: &L) -> bool {
+        self.intersection(other) != Intersection::None
+    }
+}
+
+impl<T> Linear<T> for Line<T>
+where T: PrimInt {
+    fn origin(&self) -> Point<T> {
+        self.origin
+    }
+
+    fn dir(&self) -> Point<T> {
+        self.dir
+    }
+
+    fn has_param(&self, _num: i128, _den: i128) -> bool {
+        true
+    }
+
+    fn extent(&self, _axis: usize) -> (Option<i128>, Option<i128>) {
+        (None, None)
+    }
+}
+
+impl<T> Linear<T> for Ray<T>
+where T: PrimInt {
+    fn origin(&self) -> Point<T> {
+        self.origin
+    }
+
+    fn dir(&self) -> Point<T> {
+        self.dir
+    }
+
+    fn has_param(&self, num: i128, _den: i128) -> bool {
+        num >= 0
+    }
+
+    fn extent(&self, axis: usize) -> (Option<i128>, Option<i128>) {
+        let origin = coord(to_i128(self.origin), axis);
+        match coord(to_i128(self.dir), axis).cmp(&0) {
+            Ordering::Less => (None, Some(origin)),
+            Ordering::Equal => (Some(origin), Some(origin)),
+            Ordering::Greater => (Some(origin), None),
+        }
+    }
+}
+
+impl<T> Linear<T> for Segment<T>
+where T: PrimInt + Signed {
+    fn origin(&self) -> Point<T> {
+        self.start
+    }
+
+    fn dir(&self) -> Point<T> {
+        self.end - self.start
+    }
+
+    fn has_param(&self, num: i128, den: i128) -> bool {
+        0 <= num && num <= den
+    }
+
+    fn extent(&self, axis: usize) -> (Option<i128>, Option<i128>) {
+        let a = coord(to_i128(self.start), axis);
+        let b = coord(to_i128(self.end), axis);
+        (Some(cmp::min(a, b)), Some(cmp::max(a, b)))
+    }
+}
+
+fn cross(a: Point<i128>, b: Point<i128>) -> i128 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn coord(p: Point<i128>, axis: usize) -> i128 {
+    if axis == 0 { p.0 } else { p.1 }
+}
+
+fn is_point<T: PrimInt, L: Linear<T> + ?Sized>(obj: &L) -> bool {
+    obj.dir() == Point(T::zero(), T::zero())
+}
+
+/// Intersect an object with a single point.
+fn meets_point<T: PrimInt, L: Linear<T> + ?Sized>(obj: &L, point: Point<i128>) -> Intersection {
+    let p = to_i128(obj.origin());
+    let r = to_i128(obj.dir());
+    let axis = if r.0 == 0 { 1 } else { 0 };
+
+    let (lo, hi) = obj.extent(axis);
+    let x = coord(point, axis);
+    let on_line = if is_point(obj) { point == p } else { cross(point - p, r) == 0 };
+    if on_line && lo.is_none_or(|lo| lo <= x) && hi.is_none_or(|hi| x <= hi) {
+        Intersection::Point(point.into())
+    } else {
+        Intersection::None
+    }
+}
+
+/// Get the point at parameter `t = num / den` along an object.
+fn point_at<T: PrimInt, L: Linear<T> + ?Sized>(obj: &L, num: i128, den: i128) -> RatPoint {
+    let p = to_i128(obj.origin());
+    let r = to_i128(obj.dir());
+    RatPoint::new(Point(p.0 * den + r.0 * num, p.1 * den + r.1 * num), den)
+}
+
+/// Get the lattice point on a line whose coordinate along `axis` is `value`,
+/// given that such a point is an endpoint of one of the objects.
+fn collinear_point<T: PrimInt, L: Linear<T> + ?Sized>(obj: &L, axis: usize, value: i128) -> Point<i128> {
+    let p = to_i128(obj.origin());
+    let r = to_i128(obj.dir());
+    let (dp, dr) = (value - coord(p, axis), coord(r, axis));
+    Point(p.0 + r.0 * dp / dr, p.1 + r.1 * dp / dr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ray(origin: (i64, i64), dir: (i64, i64)) -> Ray<i64> {
+        Ray { origin: origin.into(), dir: dir.into() }
+    }
+
+    fn seg(start: (i64, i64), end: (i64, i64)) -> Segment<i64> {
+        Segment { start: start.into(), end: end.into() }
+    }
+
+    #[test]
+    fn crossing_classify() {
+        let a = Line::through(Point(0_i64, 0), Point(2, 2));
+        let b = Line::through(Point(0_i64, 1), Point(2, 3));
+        let c = Line::through(Point(5_i64, 5), Point(-1, -1));
+        assert_eq!(Crossing::Parallel, a.crossing(&b));
+        assert_eq!(Crossing::Collinear, a.crossing(&c));
+
+        let d = Line::through(Point(0_i64, 4), Point(4, 0));
+        assert_eq!(Intersection::Point(Point(2, 2).into()), a.intersection(&d));
+    }
+
+    #[test]
+    fn ray_future() {
+        // hailstones A and B from the 2023 day 24 example
+        let a = ray((19, 13), (-2, 1));
+        let b = ray((18, 19), (-1, -1));
+        let crossing = a.crossing(&b);
+        assert!(crossing.in_future());
+
+        let Intersection::Point(p) = a.intersection(&b) else { panic!() };
+        assert_eq!(RatPoint::new(Point(43, 46), 3), p);
+        assert!(p.within(&Rect::new(Point(7, 7), Point(27, 27))));
+
+        // hailstones A and E cross in the past for A
+        let e = ray((20, 19), (1, -5));
+        assert!(!a.crossing(&e).in_future());
+        assert_eq!(Intersection::None, a.intersection(&e));
+    }
+
+    #[test]
+    fn segment_intersection() {
+        assert_eq!(Intersection::Point(RatPoint::new(Point(3, 3), 2)),
+                   seg((0, 0), (3, 3)).intersection(&seg((0, 3), (3, 0))));
+        assert_eq!(Intersection::None, seg((0, 0), (1, 1)).intersection(&seg((0, 3), (3, 0))));
+        assert!(seg((0, 0), (4, 0)).intersects(&seg((4, 0), (4, 4))));
+    }
+
+    #[test]
+    fn collinear_intersection() {
+        assert_eq!(Intersection::Overlap, seg((0, 0), (4, 4)).intersection(&seg((6, 6), (2, 2))));
+        assert_eq!(Intersection::Point(Point(4, 4).into()),
+                   seg((0, 0), (4, 4)).intersection(&seg((6, 6), (4, 4))));
+        assert_eq!(Intersection::None, seg((0, 0), (4, 4)).intersection(&seg((5, 5), (6, 6))));
+
+        assert_eq!(Intersection::Point(Point(0, 3).into()),
+                   ray((0, 3), (0, 1)).intersection(&ray((0, 3), (0, -2))));
+        assert_eq!(Intersection::Overlap, ray((0, 3), (0, 1)).intersection(&ray((0, 5), (0, -2))));
+        assert_eq!(Intersection::None, ray((0, 5), (0, 1)).intersection(&seg((0, 0), (0, 4))));
+    }
+
+    #[test]
+    fn degenerate_intersection() {
+        let point = seg((5, 2), (5, 2));
+        assert_eq!(Intersection::None, point.intersection(&seg((0, 0), (0, 4))));
+        assert_eq!(Intersection::Point(Point(5, 2).into()), point.intersection(&seg((5, 0), (5, 4))));
+        assert_eq!(Intersection::Point(Point(5, 2).into()), seg((5, 0), (5, 4)).intersection(&point));
+        assert_eq!(Intersection::None, point.intersection(&seg((5, 3), (5, 4))));
+        assert_eq!(Intersection::Point(Point(2, 2).into()), seg((1, 1), (3, 3)).intersection(&seg((2, 2), (2, 2))));
+
+        let line = Line::through(Point(0_i64, 0), Point(1, 2));
+        assert_eq!(Intersection::Point(Point(-3, -6).into()), seg((-3, -6), (-3, -6)).intersection(&line));
+        assert_eq!(Intersection::None, line.intersection(&seg((1, 1), (1, 1))));
+
+        let still = ray((2, 2), (0, 0));
+        assert_eq!(Intersection::Point(Point(2, 2).into()), still.intersection(&seg((2, 2), (2, 2))));
+        assert_eq!(Intersection::None, still.intersection(&ray((2, 3), (0, 0))));
+        assert_eq!(Intersection::None, ray((0, 0), (1, 1)).intersection(&ray((-1, -1), (0, 0))));
+    }
+
+    #[test]
+    fn large_coordinates() {
+        let a = ray((200_000_000_000_000, 0), (-1, 1_000));
+        let b = ray((0, 0), (1, 1));
+        let Intersection::Point(p) = a.intersection(&b) else { panic!() };
+        assert_eq!(RatPoint::new(Point(200_000_000_000_000_000, 200_000_000_000_000_000), 1001), p);
+    }
+}
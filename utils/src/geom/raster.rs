@@ -0,0 +1,98 @@
+//! Rasterization of straight lines onto the lattice.
+
+use num_traits::PrimInt;
+
+use super::{to_i128, Point};
+
+/// Iterator over the lattice points of a line between two points, inclusive
+/// of both ends (Bresenham's algorithm).
+///
+/// Horizontal, vertical and diagonal runs yield exactly the points on the
+/// line; other slopes yield the closest 8-connected approximation.
+pub struct Raster<T> {
+    curr: Option<Point<i128>>,
+    end: Point<i128>,
+    delta: Point<i128>,
+    step: Point<i128>,
+    err: i128,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> Point<T>
+where T: PrimInt {
+    /// Get all lattice points on the line from this point to another.
+    pub fn line_to(self, end: Self) -> Raster<T> {
+        let (start, end) = (to_i128(self), to_i128(end));
+
+        let delta = Point((end.0 - start.0).abs(), -(end.1 - start.1).abs());
+        let step = Point((end.0 - start.0).signum(), (end.1 - start.1).signum());
+
+        Raster {
+            curr: Some(start),
+            end,
+            delta,
+            step,
+            err: delta.0 + delta.1,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for Raster<T>
+where T: PrimInt {
+    type Item = Point<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr = self.curr?;
+
+        self.curr = if curr == self.end {
+            None
+        } else {
+            let mut next = curr;
+            let e2 = 2 * self.err;
+            if e2 >= self.delta.1 {
+                self.err += self.delta.1;
+                next.0 += self.step.0;
+            }
+            if e2 <= self.delta.0 {
+                self.err += self.delta.0;
+                next.1 += self.step.1;
+            }
+            Some(next)
+        };
+
+        Some(Point(T::from(curr.0).unwrap(), T::from(curr.1).unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn straight() {
+        let pts: Vec<_> = Point(2_usize, 5_usize).line_to(Point(2, 2)).collect();
+        assert_eq!(vec![Point(2, 5), Point(2, 4), Point(2, 3), Point(2, 2)], pts);
+
+        let pts: Vec<_> = Point(0_i32, 0).line_to(Point(3, 0)).collect();
+        assert_eq!(vec![Point(0, 0), Point(1, 0), Point(2, 0), Point(3, 0)], pts);
+
+        let pts: Vec<_> = Point(1_u8, 1).line_to(Point(1, 1)).collect();
+        assert_eq!(vec![Point(1, 1)], pts);
+    }
+
+    #[test]
+    fn diagonal() {
+        let pts: Vec<_> = Point(3_i64, 0).line_to(Point(0, 3)).collect();
+        assert_eq!(vec![Point(3, 0), Point(2, 1), Point(1, 2), Point(0, 3)], pts);
+    }
+
+    #[test]
+    fn shallow() {
+        let pts: Vec<_> = Point(0_i32, 0).line_to(Point(2, 5)).collect();
+        assert_eq!(6, pts.len());
+        assert_eq!(Some(&Point(0, 0)), pts.first());
+        assert_eq!(Some(&Point(2, 5)), pts.last());
+        assert!(pts.windows(2).all(|w| (w[1] - w[0]).abs().1 == 1));
+    }
+}
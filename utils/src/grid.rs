@@ -1,15 +1,21 @@
 use std::collections::HashMap;
 use std::cmp;
-use std::ops::{Index, IndexMut, Deref};
+use std::ops::{Index, IndexMut, Deref, Not};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
 use std::str::{Chars, FromStr};
 use std::hash::{Hash, Hasher};
 use std::fmt;
+use std::mem;
 
 use crate::convert::PeekFrom;
-use crate::geom::{Direction, Point};
+use crate::geom::{Direction, Point, Polygon, Rect};
 
 use Direction::*;
 
+pub mod image;
+mod regions;
+pub use regions::*;
+
 pub type IdxPoint = Point<usize>;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -19,6 +25,31 @@ impl Dim {
     pub fn in_bounds(&self, point: IdxPoint) -> bool {
         point.0 > 0 && point.0 <= self.0 && point.1 > 0 && point.1 <= self.1
     }
+
+    /// Count the in-bounds points within Manhattan distance `k` of `center`.
+    pub fn diamond_count(&self, center: IdxPoint, k: usize) -> usize {
+        Rect::try_from(*self).map_or(0, |rect| rect.diamond_count(center, k))
+    }
+}
+
+/// Get the rectangle of all in-bounds points, i.e. from (1, 1) to (h, w).
+/// Fails if there are no such points.
+impl TryFrom<Dim> for Rect<usize> {
+    type Error = ();
+    fn try_from(Dim(h, w): Dim) -> Result<Self, Self::Error> {
+        if h == 0 || w == 0 {
+            Err(())
+        } else {
+            Ok(Rect { min: Point(1, 1), max: Point(h, w) })
+        }
+    }
+}
+
+/// Get the dimensions of a rectangle (discarding its position).
+impl From<Rect<usize>> for Dim {
+    fn from(rect: Rect<usize>) -> Self {
+        Dim(rect.height(), rect.width())
+    }
 }
 
 /// A two-dimensional grid structure with 1-based indexing.
@@ -63,6 +94,41 @@ pub trait Grid<T> : Index<IdxPoint, Output = T> {
         self.walk(S, E)
     }
 
+    /// Find the first point (in row order) whose cell satisfies the predicate.
+    fn position<P>(&self, mut pred: P) -> Option<IdxPoint>
+        where Self: Sized,
+              P: FnMut(&T) -> bool,
+    {
+        self.walk_rows().enumerate()
+            .find(|(_, t)| pred(t))
+            .map(|(point, _)| point)
+    }
+
+    /// Find all points (in row order) whose cells satisfy the predicate.
+    fn positions<'a, P>(&'a self, mut pred: P) -> impl Iterator<Item = IdxPoint> + 'a
+        where Self: Sized,
+              T: 'a,
+              P: FnMut(&T) -> bool + 'a,
+    {
+        self.walk_rows().enumerate()
+            .filter(move |(_, t)| pred(t))
+            .map(|(point, _)| point)
+    }
+
+    /// Get a cursor to the cell at the given point, if it is in bounds.
+    fn cell(&self, point: IdxPoint) -> Option<Cell<'_, T>> where Self: Sized {
+        Cell::new(self, point)
+    }
+
+    /// Label the connected regions of the grid, where adjacent cells are in
+    /// the same region if they satisfy the predicate.
+    fn regions<F>(&self, connectivity: Connectivity, same: F) -> Regions
+        where Self: Sized,
+              F: FnMut(&T, &T) -> bool,
+    {
+        Regions::label(self, connectivity, same)
+    }
+
     fn ortho_neighbors(&self, point: IdxPoint) -> Vec<&T> {
         point.ortho_neighbors()
             .filter_map(|p| self.get(p))
@@ -76,6 +142,75 @@ pub trait Grid<T> : Index<IdxPoint, Output = T> {
     }
 }
 
+/// Drawing operations for grids whose cells can be modified in place.
+pub trait GridMut<T> : Grid<T> + IndexMut<IdxPoint, Output = T> {
+    /// Set every in-bounds cell on the line between two points.
+    fn paint_line(&mut self, from: IdxPoint, to: IdxPoint, value: T) where T: Clone {
+        for point in from.line_to(to) {
+            if self.in_bounds(point) {
+                self[point] = value.clone();
+            }
+        }
+    }
+
+    /// Set every in-bounds cell on a path through the given points.
+    fn paint_polyline(&mut self, points: &[IdxPoint], value: T) where T: Clone {
+        for pair in points.windows(2) {
+            self.paint_line(pair[0], pair[1], value.clone());
+        }
+    }
+
+    /// Set every in-bounds cell on the outline of a polygon.
+    fn paint_polygon(&mut self, vertices: &[IdxPoint], value: T) where T: Clone {
+        self.paint_polyline(vertices, value.clone());
+        if let (Some(&first), Some(&last)) = (vertices.first(), vertices.last()) {
+            self.paint_line(last, first, value);
+        }
+    }
+
+    /// Set every in-bounds cell inside or on the outline of a polygon.
+    fn fill_polygon(&mut self, vertices: &[IdxPoint], value: T) where T: Clone {
+        let polygon = Polygon::new(vertices.to_vec());
+        let Ok(grid) = Rect::try_from(self.dim()) else {
+            return;
+        };
+        let bounds = Rect::from_points(vertices.iter().copied())
+            .and_then(|bounds| bounds.intersection(&grid));
+
+        for point in bounds.iter().flat_map(Rect::points) {
+            if polygon.contains(point) {
+                self[point] = value.clone();
+            }
+        }
+    }
+
+    /// Replace the orthogonally connected region of cells equal to the one
+    /// at `start` with `value`, returning the number of cells changed.
+    fn flood_fill(&mut self, start: IdxPoint, value: T) -> usize
+        where T: Clone + PartialEq,
+    {
+        let target = match self.get(start) {
+            Some(target) if *target != value => target.clone(),
+            _ => return 0,
+        };
+
+        let mut count = 0;
+        let mut stack = vec![start];
+        while let Some(point) = stack.pop() {
+            if self.get(point) == Some(&target) {
+                self[point] = value.clone();
+                count += 1;
+                stack.extend(point.ortho_neighbors());
+            }
+        }
+
+        count
+    }
+}
+
+impl<T, G> GridMut<T> for G
+where G: Grid<T> + IndexMut<IdxPoint, Output = T> {}
+
 pub trait Walk {
     fn start(&self) -> IdxPoint;
     fn succ(&self, curr: IdxPoint) -> Option<IdxPoint>;
@@ -137,6 +272,14 @@ impl<'a, T, W: Walk> Iter<'a, T, W> {
         let curr = walk.start();
         Self { curr, walk, grid }
     }
+
+    /// Pair each cell with its position in the grid.
+    ///
+    /// Note that this shadows `Iterator::enumerate`, which yields sequential
+    /// indices rather than points.
+    pub fn enumerate(self) -> Enumerate<Self> {
+        Enumerate(self)
+    }
 }
 
 impl<'a, T, W: Walk> Iterator for Iter<'a, T, W> {
@@ -149,16 +292,128 @@ impl<'a, T, W: Walk> Iterator for Iter<'a, T, W> {
     }
 }
 
+/// Mutable iterator over the cells of a grid in the order given by a walk.
+/// Holds a borrow of every cell (see `Vec2D::iter_mut`).
+pub struct IterMut<'a, T, W: Walk> {
+    curr: IdxPoint,
+    walk: W,
+    width: usize,
+    cells: Vec<Option<&'a mut T>>,
+}
+
+impl<'a, T, W: Walk> IterMut<'a, T, W> {
+    /// Pair each cell with its position in the grid.
+    ///
+    /// Note that this shadows `Iterator::enumerate`, which yields sequential
+    /// indices rather than points.
+    pub fn enumerate(self) -> Enumerate<Self> {
+        Enumerate(self)
+    }
+}
+
+impl<'a, T, W: Walk> Iterator for IterMut<'a, T, W> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.walk.succ(self.curr).and_then(|succ| {
+            self.curr = succ;
+            let Point(row, col) = succ;
+            self.cells[(row - 1) * self.width + col - 1].take()
+        })
+    }
+}
+
+/// Iterator adapter pairing the cells of a walk with their positions.
+pub struct Enumerate<I>(I);
+
+impl<'a, T, W: Walk> Iterator for Enumerate<Iter<'a, T, W>> {
+    type Item = (IdxPoint, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|t| (self.0.curr, t))
+    }
+}
+
+impl<'a, T, W: Walk> Iterator for Enumerate<IterMut<'a, T, W>> {
+    type Item = (IdxPoint, &'a mut T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|t| (self.0.curr, t))
+    }
+}
+
+/// A cursor pointing to a single in-bounds cell of a grid.
 pub struct Cell<'a, T> {
     point: IdxPoint,
     grid : &'a dyn Grid<T>,
 }
 
-//impl<'a, T> Cell<'a, T> {
-//    fn neighbors(&self) -> Vec<Self> {
-//
-//    }
-//}
+impl<'a, T> Cell<'a, T> {
+    fn new(grid: &'a dyn Grid<T>, point: IdxPoint) -> Option<Self> {
+        if grid.in_bounds(point) {
+            Some(Self { point, grid })
+        } else {
+            None
+        }
+    }
+
+    pub fn point(&self) -> IdxPoint {
+        self.point
+    }
+
+    /// Get the value of the cell with the lifetime of the underlying grid.
+    pub fn value(&self) -> &'a T {
+        &self.grid[self.point]
+    }
+
+    /// Move one step in the given direction, or `None` if that would leave
+    /// the grid.
+    pub fn step(&self, dir: Direction) -> Option<Self> {
+        Self::new(self.grid, self.point + dir)
+    }
+
+    /// Get all in-bounds neighbors of the cell (including diagonally)
+    pub fn neighbors(&self) -> Vec<Self> {
+        self.point.neighbors()
+            .filter_map(|p| Self::new(self.grid, p))
+            .collect()
+    }
+
+    /// Get only the in-bounds orthogonal neighbors of the cell
+    pub fn ortho_neighbors(&self) -> Vec<Self> {
+        self.point.ortho_neighbors()
+            .filter_map(|p| Self::new(self.grid, p))
+            .collect()
+    }
+
+    pub fn on_edge(&self) -> bool {
+        let Point(row, col) = self.point;
+        let Dim(h, w) = self.grid.dim();
+        row == 1 || row == h || col == 1 || col == w
+    }
+
+    pub fn on_corner(&self) -> bool {
+        let Point(row, col) = self.point;
+        let Dim(h, w) = self.grid.dim();
+        (row == 1 || row == h) && (col == 1 || col == w)
+    }
+}
+
+impl<'a, T> Copy for Cell<'a, T> {}
+
+impl<'a, T> Clone for Cell<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> fmt::Debug for Cell<'a, T>
+where T: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cell")
+            .field("point", &self.point)
+            .field("value", self.value())
+            .finish()
+    }
+}
 
 impl<'a, T> Deref for Cell<'a, T> {
     type Target = T;
@@ -273,6 +528,60 @@ impl<T> Vec2D<T> {
             .map(|col| self.cells.iter()
                 .map(|row| &row[col]).collect())
     }
+
+    /// Iterate mutably over the cells in the order given by a walk.
+    ///
+    /// Since a walk may visit cells in any order, this first borrows every
+    /// cell, so creating the iterator costs O(w·h) time and memory even if
+    /// only a few cells are visited. Prefer indexing for short scans.
+    pub fn iter_mut<W: Walk>(&mut self, walk: W) -> IterMut<'_, T, W> {
+        let curr = walk.start();
+        let width = self.width;
+        let cells = self.cells.iter_mut().flatten().map(Some).collect();
+        IterMut { curr, walk, width, cells }
+    }
+
+    pub fn walk_mut(&mut self, dir0: Direction, dir1: Direction) -> IterMut<'_, T, DirectedWalk> {
+        let dim = self.dim();
+        let heading = (dir0, dir1);
+        self.iter_mut(DirectedWalk { dim, heading })
+    }
+
+    pub fn walk_rows_mut(&mut self) -> IterMut<'_, T, DirectedWalk> {
+        self.walk_mut(E, S)
+    }
+
+    pub fn walk_cols_mut(&mut self) -> IterMut<'_, T, DirectedWalk> {
+        self.walk_mut(S, E)
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Vec2D<U>
+    where F: FnMut(&T) -> U
+    {
+        let cells = self.cells.iter()
+            .map(|row| row.iter().map(&mut f).collect())
+            .collect();
+
+        Vec2D { cells, width: self.width, height: self.height }
+    }
+
+    pub fn fill(&mut self, value: T) where T: Clone {
+        for row in self.cells.iter_mut() {
+            row.fill(value.clone());
+        }
+    }
+
+    pub fn swap(&mut self, a: IdxPoint, b: IdxPoint) {
+        let (Point(r1, c1), Point(r2, c2)) = (a, b);
+        match r1.cmp(&r2) {
+            cmp::Ordering::Equal => self.cells[r1 - 1].swap(c1 - 1, c2 - 1),
+            cmp::Ordering::Less => {
+                let (top, btm) = self.cells.split_at_mut(r2 - 1);
+                mem::swap(&mut top[r1 - 1][c1 - 1], &mut btm[0][c2 - 1]);
+            }
+            cmp::Ordering::Greater => self.swap(b, a),
+        }
+    }
 }
 
 impl<T> Hash for Vec2D<T>
@@ -404,6 +713,277 @@ where T: fmt::Display
     }
 }
 
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of boolean cells with each row packed into a bitset.
+///
+/// Whole-grid operations (shifts, bitwise combinations, counting and hashing)
+/// work a word at a time, so this is much cheaper than a `Vec2D<bool>` for
+/// simulations that step or compare entire grids.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    stride: usize,
+    width: usize,
+    height: usize,
+}
+
+impl BitGrid {
+    /// Create a grid of the given dimensions with every cell unset.
+    pub fn new(dim: Dim) -> Self {
+        let Dim(height, width) = dim;
+        let stride = width.div_ceil(WORD_BITS);
+        let words = vec![0; stride * height];
+        Self { words, stride, width, height }
+    }
+
+    /// Build a grid with the cells set wherever the predicate holds.
+    pub fn from_grid<T, G, P>(grid: &G, mut pred: P) -> Self
+    where G: Grid<T>,
+          P: FnMut(&T) -> bool,
+    {
+        let mut bits = Self::new(grid.dim());
+        for (point, t) in grid.walk_rows().enumerate() {
+            if pred(t) {
+                bits.set(point, true);
+            }
+        }
+
+        bits
+    }
+
+    /// Convert to a `Vec2D` by mapping each cell.
+    pub fn to_vec2d<T, F>(&self, mut f: F) -> Vec2D<T>
+    where F: FnMut(bool) -> T
+    {
+        Vec2D::from_rows((1..=self.height).map(|row| {
+            (1..=self.width).map(|col| f(self.contains(Point(row, col)))).collect()
+        }))
+    }
+
+    pub fn contains(&self, point: IdxPoint) -> bool {
+        if self.in_bounds(point) {
+            let (idx, bit) = self.locate(point);
+            self.words[idx] & bit != 0
+        } else {
+            false
+        }
+    }
+
+    /// Set the value of a cell, returning the old value.
+    pub fn set(&mut self, point: IdxPoint, value: bool) -> bool {
+        let (idx, bit) = self.locate(point);
+        let old = self.words[idx] & bit != 0;
+        if value {
+            self.words[idx] |= bit;
+        } else {
+            self.words[idx] &= !bit;
+        }
+
+        old
+    }
+
+    /// Flip the value of a cell, returning the new value.
+    pub fn toggle(&mut self, point: IdxPoint) -> bool {
+        let (idx, bit) = self.locate(point);
+        self.words[idx] ^= bit;
+        self.words[idx] & bit != 0
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Count the number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Count the number of set cells in a single row.
+    pub fn count_row(&self, row: usize) -> usize {
+        self.row_words(row).iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Get the points of all set cells in row order.
+    pub fn ones(&self) -> impl Iterator<Item = IdxPoint> + '_ {
+        (1..=self.height).flat_map(move |row| {
+            self.row_words(row).iter().enumerate().flat_map(move |(i, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        None
+                    } else {
+                        let bit = word.trailing_zeros() as usize;
+                        word &= word - 1;
+                        Some(Point(row, i * WORD_BITS + bit + 1))
+                    }
+                })
+            })
+        })
+    }
+
+    /// Shift every cell one step in the given direction. Cells shifted past
+    /// the edge are dropped and vacated cells are unset.
+    pub fn shift(&self, dir: Direction) -> Self {
+        match dir {
+            N => self.shift_rows(true),
+            S => self.shift_rows(false),
+            E => self.shift_cols(true),
+            W => self.shift_cols(false),
+            NE => self.shift(N).shift(E),
+            NW => self.shift(N).shift(W),
+            SE => self.shift(S).shift(E),
+            SW => self.shift(S).shift(W),
+        }
+    }
+
+    fn shift_rows(&self, up: bool) -> Self {
+        let mut res = Self::new(self.dim());
+        let n = self.words.len();
+        if n > self.stride {
+            let len = n - self.stride;
+            if up {
+                res.words[..len].copy_from_slice(&self.words[self.stride..]);
+            } else {
+                res.words[self.stride..].copy_from_slice(&self.words[..len]);
+            }
+        }
+
+        res
+    }
+
+    fn shift_cols(&self, right: bool) -> Self {
+        let mut res = self.clone();
+        for row in res.words.chunks_mut(cmp::max(self.stride, 1)) {
+            if right {
+                let mut carry = 0;
+                for word in row.iter_mut() {
+                    let next = *word >> (WORD_BITS - 1);
+                    *word = (*word << 1) | carry;
+                    carry = next;
+                }
+            } else {
+                let mut carry = 0;
+                for word in row.iter_mut().rev() {
+                    let next = *word << (WORD_BITS - 1);
+                    *word = (*word >> 1) | carry;
+                    carry = next;
+                }
+            }
+        }
+        res.mask();
+
+        res
+    }
+
+    fn row_words(&self, row: usize) -> &[u64] {
+        let start = (row - 1) * self.stride;
+        &self.words[start..start + self.stride]
+    }
+
+    fn locate(&self, point: IdxPoint) -> (usize, u64) {
+        let Point(row, col) = point;
+        if !self.in_bounds(point) {
+            panic!("point {} out of bounds for bit grid", point);
+        }
+
+        let idx = (row - 1) * self.stride + (col - 1) / WORD_BITS;
+        let bit = 1 << ((col - 1) % WORD_BITS);
+        (idx, bit)
+    }
+
+    /// Clear any bits past the width of the grid.
+    fn mask(&mut self) {
+        let rem = self.width % WORD_BITS;
+        if rem != 0 {
+            let mask = (1 << rem) - 1;
+            for row in self.words.chunks_mut(self.stride) {
+                row[self.stride - 1] &= mask;
+            }
+        }
+    }
+
+    fn zip_with<F>(&mut self, rhs: &Self, f: F)
+    where F: Fn(u64, u64) -> u64
+    {
+        if self.dim() != rhs.dim() {
+            panic!("bitwise operation on bit grids of different dimensions");
+        }
+
+        for (lhs, &rhs) in self.words.iter_mut().zip(&rhs.words) {
+            *lhs = f(*lhs, rhs);
+        }
+    }
+}
+
+impl Index<IdxPoint> for BitGrid {
+    type Output = bool;
+    fn index(&self, index: IdxPoint) -> &Self::Output {
+        let (idx, bit) = self.locate(index);
+        if self.words[idx] & bit != 0 {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl Grid<bool> for BitGrid {
+    fn dim(&self) -> Dim {
+        Dim(self.height, self.width)
+    }
+}
+
+macro_rules! bitgrid_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl $assign_trait<&BitGrid> for BitGrid {
+            fn $assign_method(&mut self, rhs: &BitGrid) {
+                self.zip_with(rhs, |a, b| a $op b);
+            }
+        }
+
+        impl $trait<&BitGrid> for &BitGrid {
+            type Output = BitGrid;
+            fn $method(self, rhs: &BitGrid) -> Self::Output {
+                let mut res = self.clone();
+                res.$assign_method(rhs);
+                res
+            }
+        }
+    };
+}
+
+bitgrid_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+bitgrid_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+bitgrid_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+    fn not(self) -> Self::Output {
+        let mut res = self.clone();
+        for word in res.words.iter_mut() {
+            *word = !*word;
+        }
+        res.mask();
+
+        res
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 1..=self.height {
+            for col in 1..=self.width {
+                let ch = if self.contains(Point(row, col)) { '#' } else { '.' };
+                write!(f, "{}", ch)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -433,6 +1013,235 @@ mod tests {
         assert_eq!(vec![1, 4, 7, 2, 5, 8, 3, 6, 9], xs);
     }
 
+    #[test]
+    fn grid_enumerate() {
+        let grid = Vec2D::from_rows([
+            vec![1, 2],
+            vec![3, 4],
+        ]);
+
+        let xs: Vec<_> = grid.walk_cols().enumerate().collect();
+        assert_eq!(vec![
+            (Point(1, 1), &1), (Point(2, 1), &3),
+            (Point(1, 2), &2), (Point(2, 2), &4),
+        ], xs);
+    }
+
+    #[test]
+    fn grid_position() {
+        let grid = Vec2D::from_rows([
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            vec![7, 8, 9],
+        ]);
+
+        assert_eq!(Some(Point(3, 3)), grid.position(|&x| x == 9));
+        assert_eq!(None, grid.position(|&x| x == 10));
+
+        let evens: Vec<_> = grid.positions(|&x| x % 2 == 0).collect();
+        assert_eq!(vec![Point(1, 2), Point(2, 1), Point(2, 3), Point(3, 2)], evens);
+    }
+
+    #[test]
+    fn vec2d_walk_mut() {
+        let mut grid = Vec2D::from_rows([
+            vec![0, 0, 0],
+            vec![0, 0, 0],
+        ]);
+
+        for (x, i) in grid.walk_cols_mut().zip(1..) {
+            *x = i;
+        }
+        assert_eq!(Vec2D::from_rows([vec![1, 3, 5], vec![2, 4, 6]]), grid);
+
+        for (Point(row, col), x) in grid.walk_rows_mut().enumerate() {
+            *x += 10 * row + 100 * col;
+        }
+        assert_eq!(Vec2D::from_rows([vec![111, 213, 315], vec![122, 224, 326]]), grid);
+    }
+
+    #[test]
+    fn vec2d_map_fill_swap() {
+        let mut grid = Vec2D::from_rows([
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+        ]);
+
+        assert_eq!(Vec2D::from_rows([vec![false, true, false], vec![true, false, true]]),
+                   grid.map(|x| x % 2 == 0));
+
+        grid.swap(Point(1, 1), Point(1, 3));
+        grid.swap(Point(2, 1), Point(1, 2));
+        assert_eq!(Vec2D::from_rows([vec![3, 4, 1], vec![2, 5, 6]]), grid);
+
+        grid.fill(0);
+        assert_eq!(Vec2D::from_rows([vec![0, 0, 0], vec![0, 0, 0]]), grid);
+    }
+
+    #[test]
+    fn bitgrid_from_grid() {
+        let grid: Vec2D<char> = Vec2D::from_rows([
+            vec!['#', '.', '.'],
+            vec!['.', '#', '#'],
+        ]);
+        let bits = BitGrid::from_grid(&grid, |&ch| ch == '#');
+
+        assert_eq!(Dim(2, 3), bits.dim());
+        assert_eq!(3, bits.count_ones());
+        assert_eq!(2, bits.count_row(2));
+        assert!(bits[Point(1, 1)]);
+        assert!(!bits[Point(1, 2)]);
+
+        let ones: Vec<_> = bits.ones().collect();
+        assert_eq!(vec![Point(1, 1), Point(2, 2), Point(2, 3)], ones);
+
+        assert_eq!(grid, bits.to_vec2d(|b| if b { '#' } else { '.' }));
+    }
+
+    #[test]
+    fn bitgrid_shift() {
+        // wide enough for rows to span several words
+        let mut bits = BitGrid::new(Dim(3, 130));
+        bits.set(Point(1, 64), true);
+        bits.set(Point(2, 130), true);
+        bits.set(Point(3, 1), true);
+
+        let east: Vec<_> = bits.shift(E).ones().collect();
+        assert_eq!(vec![Point(1, 65), Point(3, 2)], east);
+
+        let west: Vec<_> = bits.shift(W).ones().collect();
+        assert_eq!(vec![Point(1, 63), Point(2, 129)], west);
+
+        let north: Vec<_> = bits.shift(N).ones().collect();
+        assert_eq!(vec![Point(1, 130), Point(2, 1)], north);
+
+        let south: Vec<_> = bits.shift(S).ones().collect();
+        assert_eq!(vec![Point(2, 64), Point(3, 130)], south);
+
+        let se: Vec<_> = bits.shift(SE).ones().collect();
+        assert_eq!(vec![Point(2, 65)], se);
+    }
+
+    #[test]
+    fn bitgrid_ops() {
+        let mut a = BitGrid::new(Dim(2, 2));
+        a.set(Point(1, 1), true);
+        a.set(Point(1, 2), true);
+
+        let mut b = BitGrid::new(Dim(2, 2));
+        b.set(Point(1, 2), true);
+        b.set(Point(2, 2), true);
+
+        assert_eq!(1, (&a & &b).count_ones());
+        assert_eq!(3, (&a | &b).count_ones());
+        assert_eq!(2, (&a ^ &b).count_ones());
+        assert_eq!(2, (!&a).count_ones());
+
+        assert!(b.toggle(Point(2, 1)));
+        assert!(b.set(Point(2, 2), false));
+        a |= &b;
+        assert_eq!(vec![Point(1, 1), Point(1, 2), Point(2, 1)], a.ones().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn grid_paint() {
+        let mut grid = Vec2D::from_rows(vec![vec!['.'; 5]; 4]);
+
+        grid.paint_polygon(&[Point(1, 1), Point(1, 4), Point(4, 4)], '#');
+        assert_eq!("####.\n.#.#.\n..##.\n...#.\n", grid.to_string());
+
+        grid.paint_line(Point(4, 2), Point(4, 9), '-');
+        assert_eq!("####.\n.#.#.\n..##.\n.----\n", grid.to_string());
+    }
+
+    #[test]
+    fn grid_fill() {
+        let mut grid = Vec2D::from_rows(vec![vec!['.'; 5]; 5]);
+        grid.fill_polygon(&[Point(1, 2), Point(1, 4), Point(5, 4), Point(5, 2)], '#');
+        assert_eq!(".###.\n".repeat(5), grid.to_string());
+
+        let mut grid = Vec2D::from_rows(vec![vec!['.'; 5]; 5]);
+        grid.paint_polygon(&[Point(1, 1), Point(1, 5), Point(5, 5), Point(5, 1)], '#');
+        assert_eq!(9, grid.flood_fill(Point(3, 3), 'o'));
+        assert_eq!("#####\n#ooo#\n#ooo#\n#ooo#\n#####\n", grid.to_string());
+        assert_eq!(0, grid.flood_fill(Point(3, 3), 'o'));
+    }
+
+    #[test]
+    fn cell_step() {
+        let grid = Vec2D::from_rows([
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+        ]);
+
+        let cell = grid.cell(Point(1, 1)).unwrap();
+        assert_eq!(1, *cell);
+        assert!(cell.step(N).is_none());
+        assert!(cell.step(W).is_none());
+
+        let cell = cell.step(SE).and_then(|cell| cell.step(E)).unwrap();
+        assert_eq!(Point(2, 3), cell.point());
+        assert_eq!(6, *cell);
+        assert!(cell.step(S).is_none());
+
+        assert!(grid.cell(Point(3, 1)).is_none());
+    }
+
+    #[test]
+    fn cell_neighbors() {
+        let grid = Vec2D::from_rows([
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            vec![7, 8, 9],
+        ]);
+
+        let mut xs: Vec<_> = grid.cell(Point(1, 2)).unwrap()
+            .neighbors().iter().map(|cell| **cell).collect();
+        xs.sort();
+        assert_eq!(vec![1, 3, 4, 5, 6], xs);
+
+        let mut xs: Vec<_> = grid.cell(Point(2, 2)).unwrap()
+            .ortho_neighbors().iter().map(|cell| **cell).collect();
+        xs.sort();
+        assert_eq!(vec![2, 4, 6, 8], xs);
+    }
+
+    #[test]
+    fn cell_edges() {
+        let grid = Vec2D::from_rows([
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            vec![7, 8, 9],
+        ]);
+
+        let center = grid.cell(Point(2, 2)).unwrap();
+        assert!(!center.on_edge());
+        assert!(!center.on_corner());
+
+        let edge = grid.cell(Point(2, 3)).unwrap();
+        assert!(edge.on_edge());
+        assert!(!edge.on_corner());
+
+        let corner = grid.cell(Point(3, 1)).unwrap();
+        assert!(corner.on_edge());
+        assert!(corner.on_corner());
+    }
+
+    #[test]
+    fn dim_rect() {
+        assert_eq!(Ok(Rect::new(Point(1, 1), Point(3, 5))), Rect::try_from(Dim(3, 5)));
+        assert_eq!(Dim(2, 4), Rect::new(Point(4, 4), Point(5, 7)).into());
+        assert_eq!(Ok(Dim(1, 7)), Rect::try_from(Dim(1, 7)).map(Dim::from));
+    }
+
+    #[test]
+    fn dim_rect_empty() {
+        assert_eq!(Err(()), Rect::try_from(Dim(0, 5)));
+        assert_eq!(Err(()), Rect::try_from(Dim(3, 0)));
+        assert_eq!(Err(()), Rect::try_from(Dim(0, 0)));
+        assert_eq!(0, Dim(0, 4).diamond_count(Point(1, 1), 3));
+    }
+
     #[test]
     fn vec2d_from_str_ok() {
         let s = "123\n456\n789";
@@ -0,0 +1,291 @@
+//! Export of grids as PPM/PNG images and numbered frame sequences.
+//!
+//! The encoders are deliberately minimal: PNG output uses uncompressed
+//! (stored) deflate blocks, which every decoder accepts and which keeps the
+//! implementation dependency-free.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use super::{Grid, IdxPoint};
+use crate::geom::Point;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Format {
+    Ppm,
+    Png,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ppm" => Some(Self::Ppm),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+        }
+    }
+}
+
+/// An RGB raster image.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Render a grid with one pixel per cell.
+    pub fn from_grid<T, G, F>(grid: &G, mut color: F) -> Self
+    where G: Grid<T>,
+          F: FnMut(IdxPoint, &T) -> Rgb,
+    {
+        let super::Dim(height, width) = grid.dim();
+        let pixels = grid.walk_rows().enumerate()
+            .map(|(point, t)| color(point, t))
+            .collect();
+
+        Self { width, height, pixels }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the color of the pixel at a 1-based (row, col) point.
+    pub fn pixel(&self, point: IdxPoint) -> Rgb {
+        let Point(row, col) = point;
+        self.pixels[(row - 1) * self.width + col - 1]
+    }
+
+    /// Blow up each pixel into a `factor`×`factor` square.
+    pub fn scale(&self, factor: usize) -> Self {
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (y / factor, x / factor)))
+            .map(|(row, col)| self.pixels[row * self.width + col])
+            .collect();
+
+        Self { width, height, pixels }
+    }
+
+    pub fn write_ppm<W: Write>(&self, mut w: W) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(self.pixels.as_flattened())
+    }
+
+    pub fn write_png<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend((self.width as u32).to_be_bytes());
+        ihdr.extend((self.height as u32).to_be_bytes());
+        // 8-bit truecolor, default compression and filtering, no interlacing
+        ihdr.extend([8, 2, 0, 0, 0]);
+        write_chunk(&mut w, b"IHDR", &ihdr)?;
+
+        let mut raw = Vec::with_capacity(self.height * (3 * self.width + 1));
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend(row.as_flattened());
+        }
+        write_chunk(&mut w, b"IDAT", &zlib_stored(&raw))?;
+
+        write_chunk(&mut w, b"IEND", &[])
+    }
+
+    pub fn write<W: Write>(&self, w: W, format: Format) -> io::Result<()> {
+        match format {
+            Format::Ppm => self.write_ppm(w),
+            Format::Png => self.write_png(w),
+        }
+    }
+
+    /// Save the image to a file, picking the format from its extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let format = Format::from_path(path).ok_or_else(|| io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported image extension: {}", path.display()),
+        ))?;
+
+        let mut w = BufWriter::new(File::create(path)?);
+        self.write(&mut w, format)?;
+        w.flush()
+    }
+}
+
+/// Writes a sequence of images as numbered files for stepping through a
+/// simulation, e.g. `frames/day14-0000.png`, `frames/day14-0001.png`, ...
+pub struct Frames {
+    dir: PathBuf,
+    prefix: String,
+    format: Format,
+    next: usize,
+}
+
+impl Frames {
+    /// Start a new sequence, creating the output directory if needed.
+    pub fn new<P: AsRef<Path>>(dir: P, prefix: &str, format: Format) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, prefix: prefix.to_string(), format, next: 0 })
+    }
+
+    /// Write the next frame, returning the path it was saved to.
+    pub fn push(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let name = format!("{}-{:04}.{}", self.prefix, self.next, self.format.extension());
+        let path = self.dir.join(name);
+        image.save(&path)?;
+        self.next += 1;
+
+        Ok(path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.next
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.next == 0
+    }
+}
+
+fn write_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+
+    let crc = crc32(kind.iter().chain(data));
+    w.write_all(&crc.to_be_bytes())
+}
+
+/// Wrap data in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32<'a, I: IntoIterator<Item = &'a u8>>(bytes: I) -> u32 {
+    let mut table = [0_u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 != 0 { 0xedb88320 ^ (c >> 1) } else { c >> 1 };
+        }
+        *entry = c;
+    }
+
+    !bytes.into_iter().fold(!0, |crc, &byte| {
+        table[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+
+    let (a, b) = bytes.iter().fold((1, 0), |(a, b), &byte| {
+        let a = (a + byte as u32) % MOD;
+        (a, (b + a) % MOD)
+    });
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Vec2D;
+
+    fn image() -> Image {
+        let grid = Vec2D::from_rows([
+            vec![true, false],
+            vec![false, true],
+        ]);
+
+        Image::from_grid(&grid, |_, &on| if on { WHITE } else { BLACK })
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(0xcbf43926, crc32(b"123456789"));
+        assert_eq!(0x11e60398, adler32(b"Wikipedia"));
+    }
+
+    #[test]
+    fn from_grid_scale() {
+        let image = image().scale(2);
+        assert_eq!((4, 4), (image.width(), image.height()));
+        assert_eq!(WHITE, image.pixel(Point(2, 2)));
+        assert_eq!(BLACK, image.pixel(Point(2, 3)));
+        assert_eq!(WHITE, image.pixel(Point(4, 3)));
+    }
+
+    #[test]
+    fn write_ppm() {
+        let mut buf = vec![];
+        image().write_ppm(&mut buf).unwrap();
+
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]);
+        assert_eq!(expected, buf);
+    }
+
+    #[test]
+    fn write_png() {
+        let mut buf = vec![];
+        image().write_png(&mut buf).unwrap();
+
+        assert_eq!(b"\x89PNG\r\n\x1a\n", &buf[..8]);
+        assert_eq!(b"IHDR", &buf[12..16]);
+        assert_eq!([0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0], buf[16..29]);
+        // IEND chunk has a fixed CRC
+        assert_eq!([0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82], buf[buf.len() - 12..]);
+    }
+
+    #[test]
+    fn zlib_stored_blocks() {
+        let data = vec![7; 70000];
+        let out = zlib_stored(&data);
+
+        // header + two block headers + data + checksum
+        assert_eq!(2 + 5 + 5 + data.len() + 4, out.len());
+        assert_eq!([0, 0xff, 0xff, 0, 0], out[2..7]);
+        assert_eq!(1, out[7 + 65535]);
+    }
+}
@@ -0,0 +1,213 @@
+//! Connected component labeling.
+
+use super::{Dim, Grid, IdxPoint, Vec2D};
+use crate::geom::{Direction, Point, Rect};
+
+use Direction::*;
+
+/// Which neighbors count as adjacent when growing a region.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Connectivity {
+    /// Orthogonal neighbors only.
+    Four,
+    /// Orthogonal and diagonal neighbors.
+    Eight,
+}
+
+/// A single connected region of a grid.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Region {
+    /// First point of the region in row order.
+    pub start: IdxPoint,
+    /// Number of cells in the region.
+    pub area: usize,
+    /// Number of cell edges separating the region from other regions or the
+    /// outside of the grid.
+    pub perimeter: usize,
+    /// Smallest rectangle containing the region.
+    pub bounds: Rect<usize>,
+    /// Number of straight sides of the region's outline (including the
+    /// outlines of any holes).
+    pub sides: usize,
+}
+
+/// Labeling of every cell of a grid by the region it belongs to.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Regions {
+    /// The index into `regions` of the region each cell belongs to.
+    pub labels: Vec2D<usize>,
+    /// All regions, ordered by their first point in row order.
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Split a grid into connected regions, where two adjacent cells are in the
+    /// same region if they satisfy the predicate.
+    pub fn label<T, G, F>(grid: &G, connectivity: Connectivity, mut same: F) -> Self
+        where G: Grid<T>,
+              F: FnMut(&T, &T) -> bool,
+    {
+        let Dim(h, w) = grid.dim();
+        let mut labels = Vec2D::from_rows(vec![vec![None; w]; h]);
+        let mut starts = vec![];
+
+        for row in 1..=h {
+            for col in 1..=w {
+                let start = Point(row, col);
+                if labels[start].is_some() {
+                    continue;
+                }
+
+                let label = starts.len();
+                starts.push(start);
+                labels[start] = Some(label);
+
+                let mut stack = vec![start];
+                while let Some(point) = stack.pop() {
+                    let adjacent: Vec<_> = match connectivity {
+                        Connectivity::Four => point.ortho_neighbors().collect(),
+                        Connectivity::Eight => point.neighbors().collect(),
+                    };
+
+                    for next in adjacent {
+                        if grid.in_bounds(next)
+                            && labels[next].is_none()
+                            && same(&grid[point], &grid[next])
+                        {
+                            labels[next] = Some(label);
+                            stack.push(next);
+                        }
+                    }
+                }
+            }
+        }
+
+        let labels = labels.map(|label| label.unwrap());
+        let regions = starts.into_iter()
+            .map(|start| Region {
+                start,
+                area: 0,
+                perimeter: 0,
+                bounds: Rect::new(start, start),
+                sides: 0,
+            })
+            .collect();
+
+        let mut regions = Self { labels, regions };
+        regions.measure();
+        regions
+    }
+
+    /// Get the region containing the given point.
+    pub fn region(&self, point: IdxPoint) -> Option<&Region> {
+        self.labels.get(point).map(|&label| &self.regions[label])
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    fn measure(&mut self) {
+        let Dim(h, w) = self.labels.dim();
+        for row in 1..=h {
+            for col in 1..=w {
+                let point = Point(row, col);
+                let label = self.labels[point];
+                let inside = |dir: Direction| {
+                    let next = point + dir;
+                    self.labels.get(next) == Some(&label)
+                };
+
+                let perimeter = [N, E, S, W].into_iter()
+                    .filter(|&dir| !inside(dir))
+                    .count();
+
+                // each side of the outline ends in exactly one corner
+                let corners = [(N, E, NE), (E, S, SE), (S, W, SW), (W, N, NW)].into_iter()
+                    .filter(|&(a, b, diag)| {
+                        let (a, b) = (inside(a), inside(b));
+                        (!a && !b) || (a && b && !inside(diag))
+                    })
+                    .count();
+
+                let region = &mut self.regions[label];
+                region.area += 1;
+                region.perimeter += perimeter;
+                region.sides += corners;
+                region.bounds = region.bounds.union(&Rect::new(point, point));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Vec2D<char> {
+        Vec2D::from_rows(s.lines().map(|line| line.trim().chars().collect()))
+    }
+
+    #[test]
+    fn label_garden() {
+        let grid = grid(
+            "AAAA
+             BBCD
+             BBCC
+             EEEC");
+
+        let regions = grid.regions(Connectivity::Four, |a, b| a == b);
+        assert_eq!(5, regions.len());
+        assert_eq!(0, regions.labels[Point(1, 4)]);
+        assert_eq!(1, regions.labels[Point(3, 2)]);
+
+        let measures: Vec<_> = regions.regions.iter()
+            .map(|r| (grid[r.start], r.area, r.perimeter, r.sides))
+            .collect();
+        assert_eq!(vec![
+            ('A', 4, 10, 4),
+            ('B', 4, 8, 4),
+            ('C', 4, 10, 8),
+            ('D', 1, 4, 4),
+            ('E', 3, 8, 4),
+        ], measures);
+
+        let c = regions.region(Point(2, 3)).unwrap();
+        assert_eq!(Rect::new(Point(2, 3), Point(4, 4)), c.bounds);
+    }
+
+    #[test]
+    fn label_holes() {
+        let grid = grid(
+            "OOOOO
+             OXOXO
+             OOOOO
+             OXOXO
+             OOOOO");
+
+        let regions = grid.regions(Connectivity::Four, |a, b| a == b);
+        assert_eq!(5, regions.len());
+        let outer = &regions.regions[0];
+        assert_eq!((21, 36, 20), (outer.area, outer.perimeter, outer.sides));
+    }
+
+    #[test]
+    fn label_diagonal() {
+        let grid = grid(
+            "#..
+             .#.
+             ..#");
+
+        let four = grid.regions(Connectivity::Four, |a, b| a == b);
+        assert_eq!(5, four.len());
+
+        let eight = grid.regions(Connectivity::Eight, |a, b| a == b);
+        assert_eq!(2, eight.len());
+        assert_eq!(3, eight.regions[0].area);
+        assert_eq!(Rect::new(Point(1, 1), Point(3, 3)), eight.regions[0].bounds);
+    }
+}
@@ -3,20 +3,76 @@ use std::cmp;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::iter::Iterator;
-use std::ops::Range;
+use std::ops::{Bound, Range, RangeInclusive};
 
+mod hyperrect;
+mod range_map;
+pub use hyperrect::*;
+pub use range_map::*;
+
+/// A half-open interval `[start, end)`.
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub struct Interval<T: PrimInt>(pub T, pub T);
 
 impl<T> Interval<T>
 where T: PrimInt
 {
+    /// Get the interval `[start, end)`.
+    pub fn half_open(start: T, end: T) -> Self {
+        Self(start, end)
+    }
+
+    /// Get the interval `[first, last]`.
+    ///
+    /// Panics if `last` is the largest value of `T`, since the interval then
+    /// has no representable end.
+    pub fn closed(first: T, last: T) -> Self {
+        Self::checked_closed(first, last).expect("overflow in Interval::closed")
+    }
+
+    /// Get the interval `[first, last]`, if its end is representable.
+    pub fn checked_closed(first: T, last: T) -> Option<Self> {
+        last.checked_add(&T::one()).map(|end| Self(first, end))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.1 <= self.0
     }
 
+    /// Get the number of values in the interval (zero if it is empty).
     pub fn len(&self) -> T {
-        self.1 - self.0
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.1 - self.0
+        }
+    }
+
+    /// Get the last value in the interval, if it is nonempty.
+    pub fn last(&self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.1 - T::one())
+        }
+    }
+
+    pub fn contains(&self, val: T) -> bool {
+        self.0 <= val && val < self.1
+    }
+
+    /// Iterate over the values in the interval, in increasing order.
+    pub fn iter(self) -> impl Iterator<Item = T> {
+        let last = self.last();
+        let first = last.map(|_| self.0);
+        std::iter::successors(first, move |&val| {
+            if Some(val) == last { None } else { Some(val + T::one()) }
+        })
+    }
+
+    /// Convert to an inclusive range, if the interval is nonempty.
+    pub fn to_inclusive(self) -> Option<RangeInclusive<T>> {
+        self.last().map(|last| self.0..=last)
     }
 
     pub fn merge(self, other: Self) -> Self {
@@ -62,6 +118,25 @@ where T: PrimInt
     }
 }
 
+impl<T> From<Range<T>> for Interval<T>
+where T: PrimInt
+{
+    fn from(range: Range<T>) -> Self {
+        Self(range.start, range.end)
+    }
+}
+
+/// Panics if the range ends at the largest value of `T` (see
+/// [`Interval::closed`]).
+impl<T> From<RangeInclusive<T>> for Interval<T>
+where T: PrimInt
+{
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (first, last) = range.into_inner();
+        Self::closed(first, last)
+    }
+}
+
 impl<T> fmt::Display for Interval<T> where T: PrimInt + fmt::Display {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "[{},{})", self.0, self.1)
@@ -104,12 +179,16 @@ where T: PrimInt
     }
 
     pub fn singleton(int: Interval<T>) -> Self {
-        Self(BTreeMap::from([(int.1, int)]))
+        Self::from([int])
     }
 
     pub fn from<const N: usize>(ints: [Interval<T>; N]) -> Self {
-        let ints = ints.iter().map(|&int| (int.1, int));
-        Self(BTreeMap::from_iter(ints))
+        let mut tree = Self::new();
+        for int in ints {
+            tree.insert(int);
+        }
+
+        tree
     }
 
     pub fn len(&self) -> usize {
@@ -120,8 +199,13 @@ where T: PrimInt
         self.0.is_empty()
     }
 
+    /// Add an interval, merging it with any intervals it overlaps or touches.
     pub fn insert(&mut self, new: Interval<T>) {
-        let keys = self.overlap_keys(new);
+        if new.is_empty() {
+            return;
+        }
+
+        let keys = self.touch_keys(new);
 
         let new = keys.iter().fold(new, |acc, key| {
             let val = self.0.remove(&key).unwrap();
@@ -129,6 +213,7 @@ where T: PrimInt
         });
 
         self.insert_raw(new);
+        self.validate_near(new.1);
     }
 
     pub fn append(&mut self, other: &Self) {
@@ -151,6 +236,40 @@ where T: PrimInt
                 self.insert_raw(right);
             }
         }
+
+        self.validate_near(rem.0);
+        self.validate_near(rem.1);
+    }
+
+    /// Check (in debug builds) that every interval is nonempty and keyed by
+    /// its end, and that no two intervals overlap or touch. Does nothing in
+    /// release builds.
+    pub fn validate(&self) {
+        Self::validate_run(self.0.iter());
+    }
+
+    /// Check (in debug builds) only the intervals next to `key`, which is all
+    /// a single insertion or removal there can disturb.
+    fn validate_near(&self, key: T) {
+        let prev = self.0.range(..key).next_back();
+        Self::validate_run(prev.into_iter().chain(self.0.range(key..).take(2)));
+    }
+
+    fn validate_run<'a, I>(run: I)
+        where I: Iterator<Item = (&'a T, &'a Interval<T>)>,
+              T: 'a,
+    {
+        if cfg!(debug_assertions) {
+            let mut prev: Option<&Interval<T>> = None;
+            for (&key, int) in run {
+                debug_assert!(key == int.1, "interval tree key is not the interval's end");
+                debug_assert!(!int.is_empty(), "empty interval in interval tree");
+                if let Some(prev) = prev {
+                    debug_assert!(prev.1 < int.0, "overlapping or adjacent intervals in interval tree");
+                }
+                prev = Some(int);
+            }
+        }
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
@@ -165,19 +284,302 @@ where T: PrimInt
         self.0.pop_last().map(|(_, v)| v)
     }
 
+    /// Check whether any interval in the tree contains the value.
+    pub fn contains(&self, val: T) -> bool {
+        self.0.range((Bound::Excluded(val), Bound::Unbounded))
+            .next()
+            .is_some_and(|(_, int)| int.0 <= val)
+    }
+
+    /// Get the total length covered by the tree.
+    pub fn covered_len(&self) -> T {
+        self.iter().fold(T::zero(), |acc, int| acc + int.len())
+    }
+
+    /// Iterate over the gaps between consecutive intervals.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.iter()
+            .zip(self.iter().skip(1))
+            .map(|(prev, next)| Interval(prev.1, next.0))
+            .filter(|gap| !gap.is_empty())
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a || b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a && b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a && !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a != b)
+    }
+
+    /// Get everything within `bounds` not covered by the tree.
+    pub fn complement(&self, bounds: Interval<T>) -> Self {
+        Self::singleton(bounds).difference(self)
+    }
+
+    /// Sweep over the endpoints of both trees in order, keeping each segment
+    /// between consecutive endpoints whose membership in each tree satisfies
+    /// the predicate. Adjacent kept segments are merged.
+    fn combine<F>(&self, other: &Self, keep: F) -> Self
+        where F: Fn(bool, bool) -> bool,
+    {
+        let mut points = Vec::with_capacity(2 * (self.len() + other.len()));
+        let mut lhs = self.iter().flat_map(|int| [int.0, int.1]).peekable();
+        let mut rhs = other.iter().flat_map(|int| [int.0, int.1]).peekable();
+        loop {
+            let next = match (lhs.peek(), rhs.peek()) {
+                (Some(a), Some(b)) if a <= b => lhs.next(),
+                (Some(_), Some(_)) => rhs.next(),
+                (Some(_), None) => lhs.next(),
+                (None, _) => rhs.next(),
+            };
+            match next {
+                Some(point) => points.push(point),
+                None => break,
+            }
+        }
+        points.dedup();
+
+        let (mut lhs, mut rhs) = (self.iter().peekable(), other.iter().peekable());
+        let mut ints: Vec<Interval<T>> = vec![];
+        for pair in points.windows(2) {
+            let (s, e) = (pair[0], pair[1]);
+            while lhs.next_if(|int| int.1 <= s).is_some() {}
+            while rhs.next_if(|int| int.1 <= s).is_some() {}
+
+            let in_lhs = lhs.peek().is_some_and(|int| int.0 <= s);
+            let in_rhs = rhs.peek().is_some_and(|int| int.0 <= s);
+            if keep(in_lhs, in_rhs) {
+                match ints.last_mut() {
+                    Some(last) if last.1 == s => last.1 = e,
+                    last => {
+                        debug_assert!(last.is_none_or(|last| last.1 < s));
+                        ints.push(Interval(s, e));
+                    },
+                }
+            }
+        }
+
+        Self(ints.into_iter().map(|int| (int.1, int)).collect())
+    }
+
     fn overlap_keys(&mut self, int: Interval<T>) -> Vec<T> {
-        self.0.range(int.0..)
+        // intervals ending exactly at `int.0` don't overlap it, and would stop
+        // the scan before reaching ones that do
+        self.0.range((Bound::Excluded(int.0), Bound::Unbounded))
             .take_while(|(_, val)| val.overlaps(int))
             .map(|(e, _)| e)
             .copied()
             .collect()
     }
 
+    /// Get the keys of all intervals overlapping or touching `int`.
+    fn touch_keys(&mut self, int: Interval<T>) -> Vec<T> {
+        self.0.range(int.0..)
+            .take_while(|(_, val)| val.0 <= int.1)
+            .map(|(e, _)| e)
+            .copied()
+            .collect()
+    }
+
     fn insert_raw(&mut self, new: Interval<T>) {
         self.0.insert(new.1, new);
     }
 }
 
+/// A piecewise translation, sending each source interval to a destination
+/// interval of the same length. Values outside every source interval map to
+/// themselves.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IntervalMap<T: PrimInt>(BTreeMap<T, (Interval<T>, T)>);
+
+impl<T> IntervalMap<T>
+where T: PrimInt
+{
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Map the interval `src` onto the interval of the same length starting
+    /// at `dest`.
+    pub fn insert(&mut self, src: Interval<T>, dest: T) {
+        if src.is_empty() {
+            return;
+        }
+
+        let before = self.0.range(..src.1).next_back();
+        if before.is_some_and(|(_, (prev, _))| prev.overlaps(src)) {
+            panic!("overlapping source intervals in IntervalMap::insert");
+        }
+
+        self.0.insert(src.0, (src, dest));
+    }
+
+    /// Iterate over the (source, destination) pairs in order of source.
+    pub fn iter(&self) -> impl Iterator<Item = (Interval<T>, Interval<T>)> + '_ {
+        self.0.values().map(|&(src, dest)| (src, Interval(dest, dest + src.len())))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, val: T) -> T {
+        match self.0.range(..=val).next_back() {
+            Some((_, &(src, dest))) if val < src.1 => dest + (val - src.0),
+            _ => val,
+        }
+    }
+
+    /// Get the image of an interval as a merged tree. The interval may be
+    /// split into several pieces, but pieces whose images touch are joined.
+    pub fn map_interval(&self, int: Interval<T>) -> IntervalTree<T> {
+        Self::merged(self.pieces(int).collect())
+    }
+
+    /// Get the image of every interval in a tree, merged as in
+    /// `map_interval`.
+    pub fn map_tree(&self, tree: &IntervalTree<T>) -> IntervalTree<T> {
+        Self::merged(tree.iter().flat_map(|&int| self.pieces(int)).collect())
+    }
+
+    fn pieces(&self, int: Interval<T>) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.split(int).into_iter().map(|(part, dest)| Self::translate(part, dest))
+    }
+
+    /// Collect the pieces of an image into a tree, joining any that overlap
+    /// or touch.
+    fn merged(mut pieces: Vec<Interval<T>>) -> IntervalTree<T> {
+        pieces.sort_by_key(|piece| piece.0);
+
+        let mut joined: Vec<Interval<T>> = vec![];
+        for piece in pieces.into_iter().filter(|piece| !piece.is_empty()) {
+            match joined.last_mut() {
+                Some(last) if piece.0 <= last.1 => last.1 = cmp::max(last.1, piece.1),
+                _ => joined.push(piece),
+            }
+        }
+
+        let mut image = IntervalTree::new();
+        for piece in joined {
+            image.insert(piece);
+        }
+
+        image
+    }
+
+    /// Get the map that applies this map, then `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        let mut composed = Self::new();
+
+        for &(src, dest) in self.0.values() {
+            let image = Interval(dest, dest + src.len());
+            for (part, out) in other.split(image) {
+                let from = Interval(src.0 + (part.0 - dest), src.0 + (part.1 - dest));
+                composed.insert_nontrivial(from, out.unwrap_or(part.0));
+            }
+        }
+
+        for &(src, dest) in other.0.values() {
+            for (part, mid) in self.split(src) {
+                if mid.is_none() {
+                    composed.insert_nontrivial(part, dest + (part.0 - src.0));
+                }
+            }
+        }
+
+        composed
+    }
+
+    /// Get the inverse map. Only meaningful if the map is one-to-one, i.e. if
+    /// the destination intervals exactly cover the source intervals.
+    pub fn invert(&self) -> Self {
+        self.iter()
+            .map(|(src, dest)| (dest, src.0))
+            .collect()
+    }
+
+    /// Split an interval into the parts covered by each source interval
+    /// (along with their destinations) and the parts left unmapped.
+    fn split(&self, int: Interval<T>) -> Vec<(Interval<T>, Option<T>)> {
+        let mut parts = vec![];
+        if int.is_empty() {
+            return parts;
+        }
+
+        let first = self.0.range(..=int.0).next_back()
+            .map(|(&start, _)| start)
+            .unwrap_or(int.0);
+
+        let mut curr = int.0;
+        for &(src, dest) in self.0.range(first..int.1).map(|(_, piece)| piece) {
+            if src.1 <= curr {
+                continue;
+            }
+            if curr < src.0 {
+                parts.push((Interval(curr, src.0), None));
+                curr = src.0;
+            }
+
+            let end = cmp::min(src.1, int.1);
+            parts.push((Interval(curr, end), Some(dest + (curr - src.0))));
+            curr = end;
+        }
+
+        if curr < int.1 {
+            parts.push((Interval(curr, int.1), None));
+        }
+
+        parts
+    }
+
+    fn translate(part: Interval<T>, dest: Option<T>) -> Interval<T> {
+        match dest {
+            Some(dest) => Interval(dest, dest + part.len()),
+            None => part,
+        }
+    }
+
+    fn insert_nontrivial(&mut self, src: Interval<T>, dest: T) {
+        if src.0 != dest {
+            self.insert(src, dest);
+        }
+    }
+}
+
+impl<T> Default for IntervalMap<T>
+where T: PrimInt
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Interval<T>, T)> for IntervalMap<T>
+where T: PrimInt
+{
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (src, dest) in iter {
+            map.insert(src, dest);
+        }
+
+        map
+    }
+}
+
 //struct Node<T: PrimInt> {
 //    value: Interval<T>,
 //    left: Option<Box<Self>>,
@@ -312,6 +714,288 @@ mod tests {
         tr.insert(i3);
 
         let is: Vec<Interval<_>> = tr.iter().copied().collect();
-        assert_eq!(vec![i1, i2, i3], is);
+        assert_eq!(vec![i1, i2.merge(i3)], is);
+    }
+
+    #[test]
+    fn interval_tree_insert_touching() {
+        let mut tr: IntervalTree<u32> = IntervalTree::from([
+            Interval(0, 3),
+            Interval(5, 7),
+        ]);
+
+        tr.insert(Interval(3, 6));
+        assert_eq!(1, tr.len());
+        assert_eq!(Some(Interval(0, 7)), tr.pop_last());
+    }
+
+    fn seed_to_soil() -> IntervalMap<u64> {
+        IntervalMap::from_iter([
+            (Interval(98, 100), 50),
+            (Interval(50, 98), 52),
+        ])
+    }
+
+    #[test]
+    fn interval_map_get() {
+        let map = seed_to_soil();
+        assert_eq!(81, map.get(79));
+        assert_eq!(14, map.get(14));
+        assert_eq!(50, map.get(98));
+        assert_eq!(100, map.get(100));
+    }
+
+    #[test]
+    fn interval_map_tree() {
+        let map = seed_to_soil();
+        let image = map.map_interval(Interval(45, 100));
+        let is: Vec<_> = image.iter().copied().collect();
+        assert_eq!(vec![Interval(45, 100)], is);
+
+        let tree = IntervalTree::from([Interval(90, 99), Interval(10, 20)]);
+        let is: Vec<_> = map.map_tree(&tree).iter().copied().collect();
+        assert_eq!(vec![Interval(10, 20), Interval(50, 51), Interval(92, 100)], is);
+
+        // disjoint sources whose images touch come out as one interval
+        let tree = IntervalTree::from([Interval(48, 50), Interval(98, 100)]);
+        let is: Vec<_> = map.map_tree(&tree).iter().copied().collect();
+        assert_eq!(vec![Interval(48, 52)], is);
+    }
+
+    #[test]
+    fn interval_map_compose() {
+        let f = seed_to_soil();
+        let g = IntervalMap::from_iter([
+            (Interval(15, 52), 0),
+            (Interval(52, 54), 37),
+            (Interval(0, 15), 39),
+        ]);
+
+        let fg = f.then(&g);
+        for val in 0..120 {
+            assert_eq!(g.get(f.get(val)), fg.get(val), "at {}", val);
+        }
+
+        let inv = fg.invert();
+        for val in 0..120 {
+            assert_eq!(val, inv.get(fg.get(val)), "at {}", val);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn interval_map_overlap() {
+        let mut map = seed_to_soil();
+        map.insert(Interval(40, 51), 0);
+    }
+
+    fn tree(ints: &[(u32, u32)]) -> IntervalTree<u32> {
+        let mut tr = IntervalTree::new();
+        for &(s, e) in ints {
+            tr.insert(Interval(s, e));
+        }
+        tr
+    }
+
+    fn ints(tr: &IntervalTree<u32>) -> Vec<(u32, u32)> {
+        tr.iter().map(|int| (int.0, int.1)).collect()
+    }
+
+    #[test]
+    fn interval_tree_set_ops() {
+        let a = tree(&[(0, 5), (8, 12), (20, 25)]);
+        let b = tree(&[(3, 9), (12, 15), (22, 23)]);
+
+        assert_eq!(vec![(0, 15), (20, 25)], ints(&a.union(&b)));
+        assert_eq!(vec![(3, 5), (8, 9), (22, 23)], ints(&a.intersection(&b)));
+        assert_eq!(vec![(0, 3), (9, 12), (20, 22), (23, 25)], ints(&a.difference(&b)));
+        assert_eq!(
+            vec![(0, 3), (5, 8), (9, 15), (20, 22), (23, 25)],
+            ints(&a.symmetric_difference(&b)),
+        );
+        assert_eq!(vec![(5, 8), (12, 20), (25, 30)], ints(&a.complement(Interval(2, 30))));
+        assert!(a.intersection(&IntervalTree::new()).is_empty());
+    }
+
+    #[test]
+    fn interval_tree_queries() {
+        let a = tree(&[(0, 5), (8, 12), (12, 14), (20, 25)]);
+        assert_eq!(16, a.covered_len());
+        assert!(a.contains(0));
+        assert!(a.contains(12));
+        assert!(!a.contains(5));
+        assert!(!a.contains(25));
+        assert!(!a.contains(17));
+
+        let gaps: Vec<_> = a.gaps().collect();
+        assert_eq!(vec![Interval(5, 8), Interval(14, 20)], gaps);
+    }
+
+    #[test]
+    fn interval_closed() {
+        assert_eq!(Interval(1_u64, 4001), Interval::closed(1, 4000));
+        assert_eq!(Interval(1_u64, 4001), Interval::from(1..=4000));
+        assert_eq!(Interval(3_u64, 7), Interval::from(3..7));
+        assert_eq!(Some(4000), Interval::closed(1_u64, 4000).last());
+        assert_eq!(Some(1..=4000), Interval::closed(1_u64, 4000).to_inclusive());
+        assert_eq!(None, Interval(5_u64, 5).to_inclusive());
+
+        assert_eq!(None, Interval::checked_closed(0, u64::MAX));
+        assert_eq!(Some(Interval(0, u64::MAX)), Interval::checked_closed(0, u64::MAX - 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn interval_closed_overflow() {
+        Interval::closed(u64::MAX - 1, u64::MAX);
+    }
+
+    #[test]
+    fn interval_iter() {
+        let vals: Vec<_> = Interval::closed(-2_i32, 2).iter().collect();
+        assert_eq!(vec![-2, -1, 0, 1, 2], vals);
+        assert_eq!(0, Interval(3_u8, 1).iter().count());
+
+        let vals: Vec<_> = Interval(u8::MAX - 2, u8::MAX).iter().collect();
+        assert_eq!(vec![253, 254], vals);
+    }
+
+    #[test]
+    fn interval_signed() {
+        let i1 = Interval(-10_i64, -3);
+        let i2 = Interval(-5_i64, 4);
+        assert_eq!(7, i1.len());
+        assert_eq!(Interval(-10, 4), i1.merge(i2));
+        assert_eq!(Interval(-5, -3), i1.intersection(i2));
+        assert_eq!((Some(Interval(-10, -5)), None), i1.diff(i2));
+        assert!(i2.contains(0));
+        assert!(!i1.contains(-3));
+        assert_eq!(0, Interval(4_i64, -4).len());
+    }
+
+    /// Seedable SplitMix64 generator, so that failing cases can be replayed.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        }
+
+        fn below(&mut self, n: u32) -> u32 {
+            (self.next() % n as u64) as u32
+        }
+
+        /// Get a random, possibly empty, interval within the model's domain.
+        fn interval(&mut self) -> Interval<u32> {
+            let s = self.below(DOMAIN + 1);
+            let len = self.below(DOMAIN / 4);
+            Interval(s, cmp::min(s + len, DOMAIN))
+        }
+
+        fn tree(&mut self) -> (IntervalTree<u32>, Model) {
+            let mut tree = IntervalTree::new();
+            let mut model = 0;
+            for _ in 0..self.below(6) {
+                let int = self.interval();
+                tree.insert(int);
+                model |= mask(int);
+            }
+            (tree, model)
+        }
+    }
+
+    /// Naive model of a set of values in `0..DOMAIN`, one bit per value.
+    type Model = u128;
+    const DOMAIN: u32 = 96;
+
+    fn mask(int: Interval<u32>) -> Model {
+        if int.is_empty() {
+            0
+        } else {
+            ((1 << int.len()) - 1) << int.0
+        }
+    }
+
+    fn runs(model: Model) -> Vec<Interval<u32>> {
+        let mut runs: Vec<Interval<u32>> = vec![];
+        for x in (0..DOMAIN).filter(|x| model >> x & 1 == 1) {
+            match runs.last_mut() {
+                Some(last) if last.1 == x => last.1 += 1,
+                _ => runs.push(Interval(x, x + 1)),
+            }
+        }
+        runs
+    }
+
+    fn check(tree: &IntervalTree<u32>, model: Model, seed: u64) {
+        tree.validate();
+        let is: Vec<_> = tree.iter().copied().collect();
+        assert_eq!(runs(model), is, "seed {}", seed);
+        assert_eq!(model.count_ones(), tree.covered_len(), "seed {}", seed);
+    }
+
+    #[test]
+    fn interval_tree_model() {
+        for seed in 0..200 {
+            let mut rng = Rng(seed);
+            let (mut tree, mut model) = rng.tree();
+            check(&tree, model, seed);
+
+            for _ in 0..50 {
+                let int = rng.interval();
+                match rng.below(5) {
+                    0 | 1 => {
+                        tree.insert(int);
+                        model |= mask(int);
+                    },
+                    2 => {
+                        tree.remove(int);
+                        model &= !mask(int);
+                    },
+                    3 => {
+                        let (other, other_model) = rng.tree();
+                        tree.append(&other);
+                        model |= other_model;
+                    },
+                    _ => {
+                        let first = runs(model).first().copied();
+                        assert_eq!(first, tree.pop_first(), "seed {}", seed);
+                        model &= !first.map_or(0, mask);
+                    },
+                }
+                check(&tree, model, seed);
+
+                let x = rng.below(DOMAIN + 1);
+                assert_eq!(model >> x & 1 == 1, tree.contains(x), "seed {}", seed);
+
+                let gaps: Vec<_> = tree.gaps().collect();
+                let hull = match (tree.iter().next(), tree.iter().last()) {
+                    (Some(first), Some(last)) => mask(Interval(first.0, last.1)),
+                    _ => 0,
+                };
+                assert_eq!(runs(hull & !model), gaps, "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn interval_tree_set_ops_model() {
+        for seed in 0..500 {
+            let mut rng = Rng(seed);
+            let (a, ma) = rng.tree();
+            let (b, mb) = rng.tree();
+
+            check(&a.union(&b), ma | mb, seed);
+            check(&a.intersection(&b), ma & mb, seed);
+            check(&a.difference(&b), ma & !mb, seed);
+            check(&a.symmetric_difference(&b), ma ^ mb, seed);
+
+            let bounds = rng.interval();
+            check(&a.complement(bounds), mask(bounds) & !ma, seed);
+        }
     }
 }
@@ -0,0 +1,265 @@
+//! Axis-aligned boxes in any number of dimensions.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use num_traits::PrimInt;
+
+use super::Interval;
+
+/// A box made of one half-open interval per axis.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct HyperRect<T: PrimInt, const N: usize>(pub [Interval<T>; N]);
+
+impl<T, const N: usize> HyperRect<T, N>
+where T: PrimInt
+{
+    /// Get the box spanning the same interval along every axis.
+    pub fn cube(int: Interval<T>) -> Self {
+        Self([int; N])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    /// Get the number of points in the box.
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.0.iter().fold(T::one(), |acc, int| acc * int.len())
+        }
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.0.iter().zip(point).all(|(int, x)| int.0 <= x && x < int.1)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.0.iter().zip(other.0).all(|(a, b)| a.overlaps(b))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut int = *self;
+        for (axis, other) in other.0.iter().enumerate() {
+            int.0[axis] = int.0[axis].intersection(*other);
+        }
+
+        if int.is_empty() { None } else { Some(int) }
+    }
+
+    /// Replace the interval along one axis.
+    pub fn with_axis(mut self, axis: usize, int: Interval<T>) -> Self {
+        self.0[axis] = int;
+        self
+    }
+
+    /// Split along an axis into the parts below and at or above `at`.
+    pub fn split(&self, axis: usize, at: T) -> (Option<Self>, Option<Self>) {
+        let Interval(min, max) = self.0[axis];
+        let below = self.with_axis(axis, Interval(min, at.min(max)));
+        let above = self.with_axis(axis, Interval(at.max(min), max));
+
+        let nonempty = |rect: Self| if rect.is_empty() { None } else { Some(rect) };
+        (nonempty(below), nonempty(above))
+    }
+
+    /// Get the parts of this box outside of another, as disjoint boxes.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if !self.overlaps(other) {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        }
+
+        let mut parts = vec![];
+        let mut rest = *self;
+        for axis in 0..N {
+            let Interval(min, max) = other.0[axis];
+            let (below, mid) = rest.split(axis, min);
+            let (mid, above) = match mid {
+                Some(mid) => mid.split(axis, max),
+                None => (None, None),
+            };
+
+            parts.extend(below);
+            parts.extend(above);
+            match mid {
+                Some(mid) => rest = mid,
+                None => break,
+            }
+        }
+
+        parts
+    }
+}
+
+impl<T, const N: usize> Index<usize> for HyperRect<T, N>
+where T: PrimInt
+{
+    type Output = Interval<T>;
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for HyperRect<T, N>
+where T: PrimInt
+{
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.0[axis]
+    }
+}
+
+impl<T, const N: usize> fmt::Display for HyperRect<T, N>
+where T: PrimInt + fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (axis, int) in self.0.iter().enumerate() {
+            if axis > 0 {
+                write!(f, "x")?;
+            }
+            write!(f, "{}", int)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A union of boxes, stored as disjoint pieces.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HyperRectSet<T: PrimInt, const N: usize>(Vec<HyperRect<T, N>>);
+
+impl<T, const N: usize> HyperRectSet<T, N>
+where T: PrimInt
+{
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    /// Add the parts of a box not already in the set.
+    pub fn insert(&mut self, rect: HyperRect<T, N>) {
+        if rect.is_empty() {
+            return;
+        }
+
+        let mut parts = vec![rect];
+        for old in &self.0 {
+            parts = parts.iter().flat_map(|part| part.subtract(old)).collect();
+        }
+
+        self.0.extend(parts);
+    }
+
+    pub fn remove(&mut self, rect: &HyperRect<T, N>) {
+        self.0 = self.0.iter().flat_map(|old| old.subtract(rect)).collect();
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.0.iter().any(|rect| rect.contains(point))
+    }
+
+    /// Get the number of points in the set.
+    pub fn volume(&self) -> T {
+        self.0.iter().fold(T::zero(), |acc, rect| acc + rect.volume())
+    }
+
+    /// Iterate over the disjoint pieces making up the set.
+    pub fn iter(&self) -> impl Iterator<Item = &HyperRect<T, N>> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T, const N: usize> Default for HyperRectSet<T, N>
+where T: PrimInt
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> FromIterator<HyperRect<T, N>> for HyperRectSet<T, N>
+where T: PrimInt
+{
+    fn from_iter<I: IntoIterator<Item = HyperRect<T, N>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for rect in iter {
+            set.insert(rect);
+        }
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(ints: [(i32, i32); 3]) -> HyperRect<i32, 3> {
+        HyperRect(ints.map(|(s, e)| Interval(s, e)))
+    }
+
+    #[test]
+    fn hyperrect_volume() {
+        assert_eq!(24, rect([(0, 2), (0, 3), (1, 5)]).volume());
+        assert_eq!(0, rect([(0, 2), (3, 3), (1, 5)]).volume());
+        assert_eq!(256, HyperRect::<u64, 4>::cube(Interval(1, 5)).volume());
+    }
+
+    #[test]
+    fn hyperrect_split() {
+        let r = rect([(0, 4), (0, 4), (0, 4)]);
+        let (below, above) = r.split(1, 1);
+        assert_eq!(Some(rect([(0, 4), (0, 1), (0, 4)])), below);
+        assert_eq!(Some(rect([(0, 4), (1, 4), (0, 4)])), above);
+
+        assert_eq!((None, Some(r)), r.split(0, -2));
+        assert_eq!((Some(r), None), r.split(2, 4));
+    }
+
+    #[test]
+    fn hyperrect_subtract() {
+        let a = rect([(0, 4), (0, 4), (0, 4)]);
+        let b = rect([(1, 2), (-3, 2), (3, 9)]);
+
+        let parts = a.subtract(&b);
+        let volume: i32 = parts.iter().map(HyperRect::volume).sum();
+        assert_eq!(a.volume() - a.intersection(&b).unwrap().volume(), volume);
+        for (i, p) in parts.iter().enumerate() {
+            assert!(!p.overlaps(&b));
+            assert!(parts[i+1..].iter().all(|q| !p.overlaps(q)));
+        }
+
+        assert_eq!(vec![a], a.subtract(&rect([(5, 6), (0, 4), (0, 4)])));
+        assert!(a.subtract(&a).is_empty());
+    }
+
+    #[test]
+    fn hyperrect_set() {
+        let mut set = HyperRectSet::new();
+        set.insert(rect([(0, 3), (0, 3), (0, 3)]));
+        set.insert(rect([(1, 4), (1, 4), (1, 4)]));
+        assert_eq!(27 + 27 - 8, set.volume());
+        assert!(set.contains([3, 3, 3]));
+        assert!(!set.contains([0, 3, 0]));
+
+        set.remove(&rect([(0, 4), (0, 4), (2, 3)]));
+        assert_eq!(27 + 27 - 8 - 14, set.volume());
+    }
+
+    #[test]
+    fn hyperrect_set_insert_empty() {
+        let mut set = HyperRectSet::new();
+        set.insert(rect([(0, 3), (2, 2), (0, 3)]));
+        assert!(set.is_empty());
+        assert_eq!(0, set.len());
+        assert_eq!(0, set.volume());
+    }
+}
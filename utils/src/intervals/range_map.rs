@@ -0,0 +1,176 @@
+//! Maps from disjoint intervals to values.
+
+use std::collections::BTreeMap;
+
+use num_traits::PrimInt;
+
+use super::Interval;
+
+/// A map assigning values to disjoint intervals of keys. Later assignments
+/// overwrite earlier ones, and adjacent intervals with equal values are
+/// merged.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RangeMap<K: PrimInt, V>(BTreeMap<K, (Interval<K>, V)>);
+
+impl<K, V> RangeMap<K, V>
+where K: PrimInt,
+      V: Clone + PartialEq,
+{
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Get the number of segments in the map.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Assign a value to every key in the interval.
+    pub fn assign(&mut self, int: Interval<K>, value: V) {
+        if int.is_empty() {
+            return;
+        }
+
+        self.clear(int);
+
+        let mut int = int;
+        let left = self.0.range(..int.0).next_back()
+            .filter(|(_, (seg, val))| seg.1 == int.0 && *val == value)
+            .map(|(&key, _)| key);
+        if let Some(key) = left {
+            let (seg, _) = self.0.remove(&key).unwrap();
+            int = seg.merge(int);
+        }
+
+        if self.0.get(&int.1).is_some_and(|(_, val)| *val == value) {
+            let (seg, _) = self.0.remove(&int.1).unwrap();
+            int = int.merge(seg);
+        }
+
+        self.0.insert(int.0, (int, value));
+    }
+
+    /// Remove any value assigned to keys in the interval.
+    pub fn clear(&mut self, int: Interval<K>) {
+        for key in self.overlap_keys(int) {
+            let (seg, val) = self.0.remove(&key).unwrap();
+            let (left, right) = seg.diff(int);
+
+            if let Some(left) = left {
+                self.0.insert(left.0, (left, val.clone()));
+            }
+            if let Some(right) = right {
+                self.0.insert(right.0, (right, val));
+            }
+        }
+    }
+
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.0.range(..=key).next_back()
+            .filter(|(_, (seg, _))| seg.contains(key))
+            .map(|(_, (_, val))| val)
+    }
+
+    /// Iterate over the segments of the map in order.
+    pub fn iter(&self) -> impl Iterator<Item = (Interval<K>, &V)> {
+        self.0.values().map(|(seg, val)| (*seg, val))
+    }
+
+    /// Iterate over the segments overlapping an interval, clipped to it.
+    pub fn range(&self, int: Interval<K>) -> impl Iterator<Item = (Interval<K>, &V)> {
+        self.overlap_keys(int).into_iter().map(move |key| {
+            let (seg, val) = &self.0[&key];
+            (seg.intersection(int), val)
+        })
+    }
+
+    /// Fold over the segments overlapping an interval, clipped to it.
+    pub fn fold<A, F>(&self, int: Interval<K>, init: A, f: F) -> A
+        where F: FnMut(A, (Interval<K>, &V)) -> A,
+    {
+        self.range(int).fold(init, f)
+    }
+
+    fn overlap_keys(&self, int: Interval<K>) -> Vec<K> {
+        if int.is_empty() {
+            return vec![];
+        }
+
+        let first = self.0.range(..int.0).next_back()
+            .filter(|(_, (seg, _))| seg.overlaps(int))
+            .map(|(&key, _)| key);
+
+        first.into_iter()
+            .chain(self.0.range(int.0..int.1).map(|(&key, _)| key))
+            .collect()
+    }
+}
+
+impl<K, V> Default for RangeMap<K, V>
+where K: PrimInt,
+      V: Clone + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(map: &RangeMap<i32, bool>) -> Vec<(i32, i32, bool)> {
+        map.iter().map(|(int, &val)| (int.0, int.1, val)).collect()
+    }
+
+    #[test]
+    fn range_map_assign() {
+        let mut map = RangeMap::new();
+        map.assign(Interval(0, 10), true);
+        map.assign(Interval(3, 5), false);
+        assert_eq!(vec![(0, 3, true), (3, 5, false), (5, 10, true)], segments(&map));
+
+        map.assign(Interval(-2, 4), false);
+        assert_eq!(vec![(-2, 5, false), (5, 10, true)], segments(&map));
+
+        map.assign(Interval(2, 8), true);
+        assert_eq!(vec![(-2, 2, false), (2, 10, true)], segments(&map));
+
+        map.assign(Interval(12, 15), true);
+        map.assign(Interval(10, 12), true);
+        assert_eq!(vec![(-2, 2, false), (2, 15, true)], segments(&map));
+
+        map.clear(Interval(0, 4));
+        assert_eq!(vec![(-2, 0, false), (4, 15, true)], segments(&map));
+    }
+
+    #[test]
+    fn range_map_get() {
+        let mut map = RangeMap::new();
+        map.assign(Interval(0_u32, 5), 'a');
+        map.assign(Interval(5, 8), 'b');
+
+        assert_eq!(Some(&'a'), map.get(0));
+        assert_eq!(Some(&'a'), map.get(4));
+        assert_eq!(Some(&'b'), map.get(5));
+        assert_eq!(None, map.get(8));
+    }
+
+    #[test]
+    fn range_map_fold() {
+        let mut map = RangeMap::new();
+        map.assign(Interval(0_u64, 10), 1_u64);
+        map.assign(Interval(4, 6), 3);
+        map.assign(Interval(20, 30), 2);
+
+        let total = map.fold(Interval(2, 25), 0, |acc, (int, &val)| acc + int.len() * val);
+        assert_eq!(2 + 2 * 3 + 4 + 5 * 2, total);
+
+        let segs: Vec<_> = map.range(Interval(5, 21)).map(|(int, _)| int).collect();
+        assert_eq!(vec![Interval(5, 6), Interval(6, 10), Interval(20, 21)], segs);
+    }
+}
@@ -9,6 +9,7 @@ pub mod geom;
 pub mod grid;
 pub mod intervals;
 pub mod math;
+pub mod render;
 pub mod types;
 
 pub mod prelude {
@@ -22,7 +23,7 @@ pub mod prelude {
     pub use super::*;
 
     pub use geom::*;
-    pub use grid::{Grid, Vec2D, Dim, IdxPoint};
+    pub use grid::{Grid, GridMut, Vec2D, BitGrid, Dim, IdxPoint, Connectivity};
     pub use intervals::*;
     pub use convert::{PeekFrom, AocString};
 
@@ -1,5 +1,12 @@
 use std::cmp;
-use num_traits::PrimInt;
+use num_traits::{PrimInt, Signed};
+
+use crate::intervals::Interval;
+
+mod ratio;
+mod linalg;
+pub use ratio::*;
+pub use linalg::*;
 
 pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
     let zero = T::zero();
@@ -42,3 +49,446 @@ pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
     let gcd = gcd(a, b);
     x * (y / gcd)
 }
+
+/// Get the inverse of `a` modulo `m`, if `a` and `m` are coprime.
+pub fn mod_inv<T: PrimInt>(a: T, m: T) -> Option<T> {
+    let m = m.to_i128().unwrap();
+    let a = a.to_i128().unwrap().rem_euclid(m);
+
+    let (g, x, _) = ext_gcd(a, m);
+    if g == 1 {
+        T::from(x.rem_euclid(m))
+    } else {
+        None
+    }
+}
+
+/// Get `base` to the power `exp` modulo `m`. Intermediate products are
+/// computed in 128 bits, so the modulus may be up to 64 bits wide.
+pub fn mod_pow<T: PrimInt>(base: T, exp: u64, m: T) -> T {
+    let m = m.to_u128().unwrap();
+    let mut base = base.to_i128().unwrap().rem_euclid(m as i128) as u128;
+    let mut exp = exp;
+    let mut acc = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = acc * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    T::from(acc).unwrap()
+}
+
+/// Combine the congruences `t ≡ a.0 (mod a.1)` and `t ≡ b.0 (mod b.1)` into
+/// a single congruence `t ≡ r (mod lcm)`, returning `(r, lcm)` with `r`
+/// reduced. The moduli need not be coprime; returns `None` if the
+/// congruences are inconsistent. Panics if the combined modulus doesn't fit
+/// in `T`.
+pub fn crt<T: PrimInt>(a: (T, T), b: (T, T)) -> Option<(T, T)> {
+    let (n1, n2) = (a.1.to_i128().unwrap(), b.1.to_i128().unwrap());
+    let a1 = a.0.to_i128().unwrap().rem_euclid(n1);
+    let a2 = b.0.to_i128().unwrap().rem_euclid(n2);
+
+    let (g, p, _) = ext_gcd(n1, n2);
+    if (a2 - a1) % g != 0 {
+        return None;
+    }
+
+    // n1 * p ≡ g (mod n2), so stepping by n1 * p * (a2 - a1) / g moves from a1 to a2
+    let lcm = (n1 / g).checked_mul(n2)
+        .filter(|&lcm| T::from(lcm).is_some())
+        .expect("combined modulus overflows in crt");
+    let step = ((a2 - a1) / g).rem_euclid(n2 / g) * p.rem_euclid(n2 / g) % (n2 / g);
+    let r = (a1 + n1 * step).rem_euclid(lcm);
+
+    Some((T::from(r).unwrap(), T::from(lcm).unwrap()))
+}
+
+/// Solve a system of congruences `t ≡ a_i (mod n_i)`, given as `(a_i, n_i)`
+/// pairs. Returns the smallest non-negative solution and the modulus of the
+/// combined congruence, or `None` if the system is inconsistent. Panics if
+/// the combined modulus doesn't fit in `T`.
+pub fn solve_congruences<T, I>(congruences: I) -> Option<(T, T)>
+    where T: PrimInt,
+          I: IntoIterator<Item = (T, T)>,
+{
+    congruences.into_iter()
+        .try_fold((T::zero(), T::one()), |acc, next| crt(acc, next))
+}
+
+/// Get the differences between consecutive terms of a sequence.
+pub fn differences<T: PrimInt + Signed>(seq: &[T]) -> Vec<T> {
+    seq.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+/// Get the degree of the lowest-degree polynomial generating a sequence,
+/// if there are enough terms to confirm it (two more than the degree, so
+/// that the constant differences are seen at least twice).
+pub fn degree<T: PrimInt + Signed>(seq: &[T]) -> Option<usize> {
+    let mut diffs = seq.to_vec();
+    for degree in 0.. {
+        if diffs.len() < 2 {
+            return None;
+        } else if diffs.iter().all(|&x| x == diffs[0]) {
+            return Some(degree);
+        }
+        diffs = differences(&diffs);
+    }
+
+    unreachable!()
+}
+
+/// Get the `n`th term of the lowest-degree polynomial sequence starting with
+/// `seq` (Newton's forward difference formula). `n` may lie before the start
+/// of the sequence (negative) or past its end.
+pub fn extrapolate<T: PrimInt + Signed>(seq: &[T], n: i64) -> T {
+    let n = n as i128;
+    let mut diffs: Vec<i128> = seq.iter().map(|x| x.to_i128().unwrap()).collect();
+
+    // sum of C(n, k) times the kth difference at the start of the sequence
+    let mut acc = 0;
+    let mut binom = 1;
+    for k in 0.. {
+        if diffs.iter().all(|&x| x == 0) {
+            break;
+        }
+
+        acc += binom * diffs[0];
+        binom = binom * (n - k) / (k + 1);
+        diffs = differences(&diffs);
+    }
+
+    T::from(acc).unwrap()
+}
+
+/// Evaluate the polynomial through the given `(x, y)` points at `x`
+/// (Lagrange's formula), if its value there is an integer.
+///
+/// Panics if two points share an x-coordinate.
+pub fn lagrange<T: PrimInt + Signed>(points: &[(T, T)], x: T) -> Option<T> {
+    let x = x.to_i128().unwrap();
+    let points: Vec<_> = points.iter()
+        .map(|(px, py)| (px.to_i128().unwrap(), py.to_i128().unwrap()))
+        .collect();
+
+    let (mut num, mut den) = (0, 1);
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let (mut term_num, mut term_den) = (yi, 1);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                if xi == xj {
+                    panic!("duplicate x-coordinate in lagrange");
+                }
+                term_num *= x - xj;
+                term_den *= xi - xj;
+            }
+        }
+
+        num = num * term_den + term_num * den;
+        den *= term_den;
+
+        let g = gcd(num, den).abs();
+        if g > 1 {
+            (num, den) = (num / g, den / g);
+        }
+    }
+
+    if num % den == 0 {
+        T::from(num / den)
+    } else {
+        None
+    }
+}
+
+/// Sample `f` at `start`, `start + step`, ..., `start + degree * step` and
+/// extrapolate the polynomial through those samples to `target`, which must
+/// be `start` plus a multiple of `step`.
+pub fn extrapolate_samples<T, F>(start: u64, step: u64, degree: usize, target: u64, mut f: F) -> T
+    where T: PrimInt + Signed,
+          F: FnMut(u64) -> T,
+{
+    if step == 0 {
+        panic!("zero step in extrapolate_samples");
+    } else if !target.abs_diff(start).is_multiple_of(step) {
+        panic!("target not aligned with samples in extrapolate_samples");
+    }
+
+    let samples: Vec<T> = (0..=degree as u64)
+        .map(|i| f(start + i * step))
+        .collect();
+
+    let n = (target as i64 - start as i64) / step as i64;
+    extrapolate(&samples, n)
+}
+
+/// Get the integer square root, rounded down. Panics if `n` is negative.
+pub fn isqrt<T: PrimInt>(n: T) -> T {
+    if n < T::zero() {
+        panic!("negative argument to isqrt");
+    } else if n < T::from(2).unwrap() {
+        return n;
+    }
+
+    // Newton's method from an initial guess no smaller than the root
+    let bits = T::zero().count_zeros() - n.leading_zeros();
+    let mut x = T::one() << bits.div_ceil(2) as usize;
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Get the integer `k`-th root, rounded down. Panics if `n` is negative or
+/// `k` is zero.
+pub fn iroot<T: PrimInt>(n: T, k: u32) -> T {
+    if k == 0 {
+        panic!("zeroth root in iroot");
+    } else if n < T::zero() {
+        panic!("negative argument to iroot");
+    } else if k == 1 {
+        return n;
+    }
+
+    // binary search for the largest root whose power doesn't exceed n
+    let bits = T::zero().count_zeros() - n.leading_zeros();
+    let (mut lo, mut hi) = (T::zero(), T::one() << (bits / k + 1) as usize);
+    while hi - lo > T::one() {
+        let mid = lo + (hi - lo) / T::from(2).unwrap();
+        match num_traits::checked_pow(mid, k as usize) {
+            Some(pow) if pow <= n => lo = mid,
+            _ => hi = mid,
+        }
+    }
+
+    lo
+}
+
+/// Get the integers `x` with `a·x² + b·x + c > 0`. `a` must be negative, so
+/// that the solutions form a single bounded interval.
+///
+/// Everything is computed exactly in `i128`, so coefficients below 2^62 in
+/// magnitude are safe. Panics if an intermediate value still overflows.
+pub fn quadratic_gt<T: PrimInt + Signed>(a: T, b: T, c: T) -> Interval<T> {
+    quadratic_solutions(a, b, c, |y| y > 0)
+}
+
+/// Get the integers `x` with `a·x² + b·x + c ≥ 0`, under the same conditions
+/// as `quadratic_gt`.
+pub fn quadratic_ge<T: PrimInt + Signed>(a: T, b: T, c: T) -> Interval<T> {
+    quadratic_solutions(a, b, c, |y| y >= 0)
+}
+
+fn quadratic_solutions<T, F>(a: T, b: T, c: T, keep: F) -> Interval<T>
+    where T: PrimInt + Signed,
+          F: Fn(i128) -> bool,
+{
+    const OVERFLOW: &str = "overflow in quadratic solver";
+
+    if a >= T::zero() {
+        panic!("non-negative leading coefficient in quadratic solver");
+    }
+
+    let (a, b, c) = (a.to_i128().unwrap(), b.to_i128().unwrap(), c.to_i128().unwrap());
+    let disc = a.checked_mul(c).and_then(|ac| ac.checked_mul(4))
+        .and_then(|ac4| b.checked_mul(b)?.checked_sub(ac4))
+        .expect(OVERFLOW);
+    if disc < 0 {
+        return Interval(T::zero(), T::zero());
+    }
+
+    // the rounded roots are within one of the integer bounds, so nudge them
+    // onto the exact boundary by checking the inequality directly
+    let sat = |x: i128| {
+        let y = a.checked_mul(x)
+            .and_then(|y| y.checked_add(b)?.checked_mul(x)?.checked_add(c))
+            .expect(OVERFLOW);
+        keep(y)
+    };
+    let s = isqrt(disc);
+    let mut lo = Ratio::new(b - s, -2 * a).floor();
+    let mut hi = Ratio::new(b + s, -2 * a).floor();
+
+    while sat(lo - 1) {
+        lo -= 1;
+    }
+    while sat(hi + 1) {
+        hi += 1;
+    }
+    while lo <= hi && !sat(lo) {
+        lo += 1;
+    }
+    while lo <= hi && !sat(hi) {
+        hi -= 1;
+    }
+
+    let narrow = |x: i128| T::from(x).expect(OVERFLOW);
+    if lo > hi { Interval(narrow(lo), narrow(lo)) } else { Interval(narrow(lo), narrow(hi + 1)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modular_inverse() {
+        assert_eq!(Some(4), mod_inv(3, 11));
+        assert_eq!(Some(7), mod_inv(-3_i64, 11));
+        assert_eq!(None, mod_inv(6_u32, 9));
+        for a in 1..13_u64 {
+            assert_eq!(1, a * mod_inv(a, 13).unwrap() % 13);
+        }
+    }
+
+    #[test]
+    fn modular_pow() {
+        assert_eq!(445, mod_pow(4, 13, 497));
+        assert_eq!(1, mod_pow(7_u64, 0, 13));
+        assert_eq!(0, mod_pow(7_u64, 5, 1));
+        assert_eq!(4, mod_pow(-2_i32, 2, 7));
+
+        let p = (1_u64 << 61) - 1;
+        assert_eq!(1, mod_pow(3, p - 1, p));
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(Some((23, 105)), solve_congruences([(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt((4_u64, 6), (2, 4)));
+        assert_eq!(None, crt((1_u64, 6), (2, 4)));
+        assert_eq!(Some((0, 1)), solve_congruences::<i64, _>([]));
+
+        // non-zero offsets with shared factors
+        let system = [(3_u64, 10), (13, 15), (7, 12)];
+        let (t, m) = solve_congruences(system).unwrap();
+        assert_eq!(60, m);
+        assert!(system.iter().all(|&(a, n)| t % n == a % n));
+    }
+
+    #[test]
+    #[should_panic(expected = "combined modulus overflows in crt")]
+    fn chinese_remainder_overflow() {
+        crt((1_u8, 16), (2, 17));
+    }
+
+    #[test]
+    fn finite_differences() {
+        assert_eq!(vec![3, 3, 3], differences(&[0, 3, 6, 9]));
+        assert_eq!(Some(0), degree(&[5, 5]));
+        assert_eq!(Some(1), degree(&[0, 3, 6, 9, 12, 15]));
+        assert_eq!(Some(3), degree(&[10, 13, 16, 21, 30, 45]));
+        assert_eq!(None, degree(&[1, 2, 4, 8, 16]));
+        assert_eq!(None, degree::<i32>(&[7]));
+        assert_eq!(None, degree(&[0, 3]));
+        assert_eq!(Some(1), degree(&[0, 3, 6]));
+    }
+
+    #[test]
+    fn polynomial_extrapolate() {
+        let seq = [10_i64, 13, 16, 21, 30, 45];
+        assert_eq!(68, extrapolate(&seq, 6));
+        assert_eq!(5, extrapolate(&seq, -1));
+        assert_eq!(16, extrapolate(&seq, 2));
+
+        let squares: Vec<i64> = (0..4).map(|n| n * n).collect();
+        assert_eq!(10_000, extrapolate(&squares, 100));
+        assert_eq!(49, extrapolate(&squares, -7));
+    }
+
+    #[test]
+    fn polynomial_lagrange() {
+        // y = x^2 - 3x + 1
+        let points = [(-1_i64, 5), (2, -1), (4, 5)];
+        assert_eq!(Some(1), lagrange(&points, 0));
+        assert_eq!(Some(71), lagrange(&points, 10));
+
+        // y = x / 2
+        let points = [(0_i32, 0), (2, 1)];
+        assert_eq!(Some(2), lagrange(&points, 4));
+        assert_eq!(None, lagrange(&points, 3));
+    }
+
+    #[test]
+    fn sample_extrapolate() {
+        let mut calls = 0;
+        let f = |n: u64| {
+            calls += 1;
+            3 * (n as i64).pow(2) + 1
+        };
+        assert_eq!(3 * 26501365_i64.pow(2) + 1, extrapolate_samples(65, 131, 2, 26501365, f));
+        assert_eq!(3, calls);
+    }
+
+    #[test]
+    #[should_panic(expected = "zero step in extrapolate_samples")]
+    fn sample_extrapolate_zero_step() {
+        extrapolate_samples(5, 0, 2, 5, |n| n as i64);
+    }
+
+    #[test]
+    fn integer_roots() {
+        for n in 0..1000_u64 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n);
+        }
+        assert_eq!(u64::MAX >> 32, isqrt(u64::MAX));
+        assert_eq!(u128::MAX >> 64, isqrt(u128::MAX));
+        assert_eq!(1 << 50, isqrt(1_u128 << 100));
+        assert_eq!((1 << 50) - 1, isqrt((1_u128 << 100) - 1));
+        assert_eq!(3, isqrt(9_i32));
+
+        assert_eq!(4, iroot(64_u32, 3));
+        assert_eq!(3, iroot(63_u32, 3));
+        assert_eq!(2, iroot(u64::MAX, 63));
+        assert_eq!(1, iroot(u64::MAX, 64));
+        assert_eq!(2642245, iroot(u64::MAX, 3));
+        assert_eq!(17, iroot(17_u8, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow in quadratic solver")]
+    fn quadratic_overflow() {
+        quadratic_gt(-1, i128::MAX / 2, 0);
+    }
+
+    #[test]
+    fn quadratic_inequalities() {
+        // x (7 - x) > 9
+        assert_eq!(Interval(2, 6), quadratic_gt(-1, 7, -9));
+        // x (30 - x) > 200 touches zero at both ends
+        assert_eq!(Interval(11, 20), quadratic_gt(-1_i64, 30, -200));
+        assert_eq!(Interval(10, 21), quadratic_ge(-1_i64, 30, -200));
+
+        // a double root only satisfies the non-strict inequality
+        assert!(quadratic_gt(-1, 4, -4).is_empty());
+        assert_eq!(Interval(2, 3), quadratic_ge(-1, 4, -4));
+        assert!(quadratic_ge(-1, 0, -1).is_empty());
+
+        // large enough that f64 loses precision
+        let t: i128 = 1 << 60;
+        let c = -(t / 2) * (t / 2) + 1;
+        assert_eq!(Interval(t / 2, t / 2 + 1), quadratic_gt(-1, t, c));
+
+        // b² overflows i64 but not the i128 working values
+        let t: i64 = 1 << 32;
+        let c = -(t / 2) * (t / 2) + 1;
+        assert_eq!(Interval(t / 2, t / 2 + 1), quadratic_gt(-1, t, c));
+        assert_eq!(Interval(0, t + 1), quadratic_ge(-1, t, 0));
+
+        for a in -3..0_i64 {
+            for b in -10..10 {
+                for c in -10..10 {
+                    let sols: Vec<_> = (-20..20).filter(|x| a*x*x + b*x + c > 0).collect();
+                    assert_eq!(sols, quadratic_gt(a, b, c).iter().collect::<Vec<_>>());
+                    let sols: Vec<_> = (-20..20).filter(|x| a*x*x + b*x + c >= 0).collect();
+                    assert_eq!(sols, quadratic_ge(a, b, c).iter().collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+}
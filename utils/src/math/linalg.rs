@@ -0,0 +1,194 @@
+//! Exact linear algebra on small dense matrices.
+
+use num_traits::{PrimInt, Signed};
+
+use super::Ratio;
+
+/// The solutions of a system of linear equations.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Solution<T> {
+    Unique(Vec<T>),
+    None,
+    Infinite,
+}
+
+/// Solve the system `a · x = b` by Gaussian elimination. `a` has one row per
+/// equation and one column per unknown.
+pub fn solve<T>(a: &[Vec<Ratio<T>>], b: &[Ratio<T>]) -> Solution<Ratio<T>>
+where T: PrimInt + Signed {
+    if a.len() != b.len() {
+        panic!("mismatched arguments to solve");
+    }
+
+    let unknowns = a.first().map_or(0, Vec::len);
+    let mut rows: Vec<Vec<Ratio<T>>> = a.iter().zip(b)
+        .map(|(row, &rhs)| {
+            if row.len() != unknowns {
+                panic!("non-rectangular argument to solve");
+            }
+            let mut row = row.clone();
+            row.push(rhs);
+            row
+        })
+        .collect();
+
+    let pivots = reduce(&mut rows, unknowns);
+
+    // a leftover row reading 0 = c (c nonzero) can't be satisfied
+    if rows[pivots.len()..].iter().any(|row| !row[unknowns].is_zero()) {
+        Solution::None
+    } else if pivots.len() < unknowns {
+        Solution::Infinite
+    } else {
+        Solution::Unique(rows.iter().take(unknowns).map(|row| row[unknowns]).collect())
+    }
+}
+
+/// Get the determinant of a square integer matrix (Bareiss algorithm, so
+/// every intermediate value is itself a minor of the matrix).
+pub fn determinant<T>(m: &[Vec<T>]) -> T
+where T: PrimInt + Signed {
+    let n = m.len();
+    if m.iter().any(|row| row.len() != n) {
+        panic!("non-square argument to determinant");
+    }
+
+    let mut m = m.to_vec();
+    let mut sign = T::one();
+    let mut prev = T::one();
+    for k in 0..n {
+        if m[k][k].is_zero() {
+            match (k+1..n).find(|&i| !m[i][k].is_zero()) {
+                Some(i) => {
+                    m.swap(i, k);
+                    sign = -sign;
+                },
+                None => return T::zero(),
+            }
+        }
+
+        for i in k+1..n {
+            for j in k+1..n {
+                m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / prev;
+            }
+        }
+        prev = m[k][k];
+    }
+
+    match n {
+        0 => T::one(),
+        _ => sign * m[n-1][n-1],
+    }
+}
+
+/// Get the inverse of a square matrix, if it is invertible.
+pub fn inverse<T>(m: &[Vec<Ratio<T>>]) -> Option<Vec<Vec<Ratio<T>>>>
+where T: PrimInt + Signed {
+    let n = m.len();
+    if m.iter().any(|row| row.len() != n) {
+        panic!("non-square argument to inverse");
+    }
+
+    let mut rows: Vec<Vec<Ratio<T>>> = m.iter().enumerate()
+        .map(|(i, row)| {
+            let mut row = row.clone();
+            row.extend((0..n).map(|j| if i == j { Ratio::one() } else { Ratio::zero() }));
+            row
+        })
+        .collect();
+
+    if reduce(&mut rows, n).len() < n {
+        None
+    } else {
+        Some(rows.into_iter().map(|row| row[n..].to_vec()).collect())
+    }
+}
+
+/// Bring the first `cols` columns of a matrix into reduced row echelon form,
+/// returning the pivot column of each nonzero row.
+fn reduce<T>(rows: &mut [Vec<Ratio<T>>], cols: usize) -> Vec<usize>
+where T: PrimInt + Signed {
+    let mut pivots = vec![];
+    for col in 0..cols {
+        let r = pivots.len();
+        let Some(pivot) = (r..rows.len()).find(|&i| !rows[i][col].is_zero()) else {
+            continue;
+        };
+        rows.swap(r, pivot);
+
+        let scale = rows[r][col].recip();
+        for x in rows[r].iter_mut() {
+            *x *= scale;
+        }
+
+        for i in 0..rows.len() {
+            let factor = rows[i][col];
+            if i != r && !factor.is_zero() {
+                for j in 0..rows[i].len() {
+                    let sub = factor * rows[r][j];
+                    rows[i][j] -= sub;
+                }
+            }
+        }
+
+        pivots.push(col);
+    }
+
+    pivots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(rows: &[&[i128]]) -> Vec<Vec<Ratio<i128>>> {
+        rows.iter()
+            .map(|row| row.iter().map(|&x| Ratio::from(x)).collect())
+            .collect()
+    }
+
+    fn vector(xs: &[i128]) -> Vec<Ratio<i128>> {
+        xs.iter().map(|&x| Ratio::from(x)).collect()
+    }
+
+    #[test]
+    fn solve_unique() {
+        let a = matrix(&[&[2, 1, -1], &[-3, -1, 2], &[-2, 1, 2]]);
+        let b = vector(&[8, -11, -3]);
+        assert_eq!(Solution::Unique(vector(&[2, 3, -1])), solve(&a, &b));
+
+        let a = matrix(&[&[2, 0], &[0, 3]]);
+        let b = vector(&[1, 1]);
+        assert_eq!(Solution::Unique(vec![Ratio::new(1, 2), Ratio::new(1, 3)]), solve(&a, &b));
+    }
+
+    #[test]
+    fn solve_degenerate() {
+        let a = matrix(&[&[1, 2], &[2, 4]]);
+        assert_eq!(Solution::Infinite, solve(&a, &vector(&[3, 6])));
+        assert_eq!(Solution::None, solve(&a, &vector(&[3, 7])));
+
+        // overdetermined but consistent
+        let a = matrix(&[&[1, 0], &[0, 1], &[1, 1]]);
+        assert_eq!(Solution::Unique(vector(&[1, 2])), solve(&a, &vector(&[1, 2, 3])));
+    }
+
+    #[test]
+    fn integer_determinant() {
+        assert_eq!(-2, determinant(&[vec![1_i64, 2], vec![3, 4]]));
+        assert_eq!(0, determinant(&[vec![1_i64, 2], vec![2, 4]]));
+        assert_eq!(-306, determinant(&[vec![6_i64, 1, 1], vec![4, -2, 5], vec![2, 8, 7]]));
+        assert_eq!(-1, determinant(&[vec![0_i32, 1], vec![1, 0]]));
+        assert_eq!(1, determinant::<i32>(&[]));
+    }
+
+    #[test]
+    fn matrix_inverse() {
+        let m = matrix(&[&[4, 7], &[2, 6]]);
+        let inv = inverse(&m).unwrap();
+        let r = |n, d| Ratio::new(n, d);
+        assert_eq!(vec![vec![r(3, 5), r(-7, 10)], vec![r(-1, 5), r(2, 5)]], inv);
+
+        assert_eq!(None, inverse(&matrix(&[&[1, 2], &[2, 4]])));
+    }
+}
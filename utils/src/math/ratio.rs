@@ -0,0 +1,244 @@
+//! Exact rational numbers.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg};
+
+use num_traits::{PrimInt, Signed};
+
+use super::gcd;
+
+/// A fraction in lowest terms, with a positive denominator.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Ratio<T> {
+    num: T,
+    den: T,
+}
+
+impl<T> Ratio<T>
+where T: PrimInt + Signed {
+    pub fn new(num: T, den: T) -> Self {
+        if den.is_zero() {
+            panic!("zero denominator in Ratio::new");
+        }
+
+        let g = gcd(num, den).abs();
+        let sign = den.signum();
+        Self { num: sign * num / g, den: sign * den / g }
+    }
+
+    pub fn from_integer(num: T) -> Self {
+        Self { num, den: T::one() }
+    }
+
+    pub fn zero() -> Self {
+        Self::from_integer(T::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::from_integer(T::one())
+    }
+
+    pub fn numer(&self) -> T {
+        self.num
+    }
+
+    pub fn denom(&self) -> T {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den.is_one()
+    }
+
+    /// Get the value as an integer, if it is one.
+    pub fn to_integer(&self) -> Option<T> {
+        if self.is_integer() { Some(self.num) } else { None }
+    }
+
+    /// Round down to the nearest integer.
+    pub fn floor(&self) -> T {
+        let q = self.num / self.den;
+        if self.num < T::zero() && q * self.den != self.num { q - T::one() } else { q }
+    }
+
+    /// Round up to the nearest integer.
+    pub fn ceil(&self) -> T {
+        -(-*self).floor()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self { num: self.num.abs(), den: self.den }
+    }
+
+    /// Get the reciprocal. Panics if the value is zero.
+    pub fn recip(&self) -> Self {
+        Self::new(self.den, self.num)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.num.to_f64().unwrap() / self.den.to_f64().unwrap()
+    }
+}
+
+impl<T> From<T> for Ratio<T>
+where T: PrimInt + Signed {
+    fn from(num: T) -> Self {
+        Self::from_integer(num)
+    }
+}
+
+impl<T> Default for Ratio<T>
+where T: PrimInt + Signed {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<T> Add for Ratio<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        let g = gcd(self.den, other.den);
+        let num = self.num * (other.den / g) + other.num * (self.den / g);
+        Self::new(num, self.den / g * other.den)
+    }
+}
+
+impl<T> Sub for Ratio<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
+
+impl<T> Mul for Ratio<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self::Output {
+        // cancel before multiplying to keep intermediate values small
+        let g1 = gcd(self.num, other.den).abs();
+        let g2 = gcd(other.num, self.den).abs();
+        Self {
+            num: (self.num / g1) * (other.num / g2),
+            den: (self.den / g2) * (other.den / g1),
+        }
+    }
+}
+
+impl<T> Div for Ratio<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn div(self, other: Self) -> Self::Output {
+        if other.is_zero() {
+            panic!("division by zero in Ratio::div");
+        }
+
+        let g1 = gcd(self.num, other.num).abs();
+        let g2 = gcd(self.den, other.den);
+        Self::new((self.num / g1) * (other.den / g2), (self.den / g2) * (other.num / g1))
+    }
+}
+
+impl<T> Neg for Ratio<T>
+where T: PrimInt + Signed {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self { num: -self.num, den: self.den }
+    }
+}
+
+macro_rules! ratio_assign {
+    ($trait:ident, $fn:ident, $op:tt) => {
+        impl<T> $trait for Ratio<T>
+        where T: PrimInt + Signed {
+            fn $fn(&mut self, other: Self) {
+                *self = *self $op other;
+            }
+        }
+    };
+}
+
+ratio_assign!(AddAssign, add_assign, +);
+ratio_assign!(SubAssign, sub_assign, -);
+ratio_assign!(MulAssign, mul_assign, *);
+ratio_assign!(DivAssign, div_assign, /);
+
+impl<T> Ord for Ratio<T>
+where T: PrimInt + Signed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self - *other).num.cmp(&T::zero())
+    }
+}
+
+impl<T> PartialOrd for Ratio<T>
+where T: PrimInt + Signed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> fmt::Display for Ratio<T>
+where T: PrimInt + Signed + fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Ratio<i128> {
+        Ratio::new(num, den)
+    }
+
+    #[test]
+    fn ratio_normalize() {
+        assert_eq!(r(1, 2), r(3, 6));
+        assert_eq!(r(-1, 2), r(3, -6));
+        assert_eq!(r(0, 1), r(0, -5));
+        assert_eq!((-1, 2), (r(2, -4).numer(), r(2, -4).denom()));
+    }
+
+    #[test]
+    fn ratio_arith() {
+        assert_eq!(r(5, 6), r(1, 2) + r(1, 3));
+        assert_eq!(r(1, 6), r(1, 2) - r(1, 3));
+        assert_eq!(r(1, 6), r(1, 2) * r(1, 3));
+        assert_eq!(r(3, 2), r(1, 2) / r(1, 3));
+        assert_eq!(r(-2, 1), -r(4, 2));
+
+        let mut x = r(1, 4);
+        x += r(3, 4);
+        x *= r(5, 1);
+        assert_eq!(Some(5), x.to_integer());
+        assert_eq!(None, r(5, 3).to_integer());
+    }
+
+    #[test]
+    fn ratio_order() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert_eq!(Some(&r(7, 2)), [r(1, 2), r(7, 2), r(-9, 1)].iter().max());
+
+        assert_eq!((3, 4), (r(7, 2).floor(), r(7, 2).ceil()));
+        assert_eq!((-4, -3), (r(-7, 2).floor(), r(-7, 2).ceil()));
+        assert_eq!((2, 2), (r(2, 1).floor(), r(2, 1).ceil()));
+    }
+
+    #[test]
+    fn ratio_display() {
+        assert_eq!("-3/4", r(6, -8).to_string());
+        assert_eq!("5", r(10, 2).to_string());
+    }
+}
@@ -0,0 +1,237 @@
+//! Terminal rendering of grids with overlays.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, IsTerminal};
+
+pub use colored::Color;
+
+use crate::geom::{Point, Rect};
+use crate::grid::{Grid, IdxPoint};
+
+/// A layer of marks drawn on top of a grid.
+///
+/// Each marked point may replace the underlying glyph, color it, or both.
+/// Layers added later are drawn over earlier ones.
+#[derive(Debug, Clone, Default)]
+pub struct Layer {
+    marks: HashMap<IdxPoint, Mark>,
+    glyph: Option<char>,
+    color: Option<Color>,
+    bg: Option<Color>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Mark {
+    glyph: Option<char>,
+    color: Option<Color>,
+}
+
+impl Layer {
+    /// Mark a set of points, keeping their underlying glyphs unless a glyph
+    /// is set with [`Layer::glyph`].
+    pub fn points<I: IntoIterator<Item = IdxPoint>>(points: I) -> Self {
+        let marks = points.into_iter()
+            .map(|point| (point, Mark::default()))
+            .collect();
+
+        Self { marks, ..Self::default() }
+    }
+
+    /// Draw a specific glyph at each point.
+    pub fn chars<I: IntoIterator<Item = (IdxPoint, char)>>(chars: I) -> Self {
+        let marks = chars.into_iter()
+            .map(|(point, ch)| (point, Mark { glyph: Some(ch), color: None }))
+            .collect();
+
+        Self { marks, ..Self::default() }
+    }
+
+    /// Color each point individually, keeping the underlying glyphs.
+    pub fn colors<I: IntoIterator<Item = (IdxPoint, Color)>>(colors: I) -> Self {
+        let marks = colors.into_iter()
+            .map(|(point, color)| (point, Mark { glyph: None, color: Some(color) }))
+            .collect();
+
+        Self { marks, ..Self::default() }
+    }
+
+    /// Draw every point in the layer with the given glyph.
+    pub fn glyph(mut self, glyph: char) -> Self {
+        self.glyph = Some(glyph);
+        self
+    }
+
+    /// Set the foreground color of every point that has no color of its own.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the background color of every point in the layer.
+    pub fn on_color(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+}
+
+/// Renders a grid and its overlays as (optionally colored) text.
+///
+/// Colors are only emitted when stdout is a terminal, unless overridden with
+/// [`Renderer::colored`].
+pub struct Renderer<'a, T> {
+    grid: &'a dyn Grid<T>,
+    layers: Vec<Layer>,
+    view: Option<Rect<usize>>,
+    colored: bool,
+}
+
+impl<'a, T> Renderer<'a, T>
+where T: fmt::Display
+{
+    pub fn new(grid: &'a dyn Grid<T>) -> Self {
+        let colored = io::stdout().is_terminal();
+        Self { grid, layers: vec![], view: None, colored }
+    }
+
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Only render the cells within `radius` rows and columns of `focus`.
+    pub fn window(self, focus: IdxPoint, radius: usize) -> Self {
+        self.crop(Rect::new(focus, focus).expand(radius))
+    }
+
+    /// Only render the cells within the given rectangle.
+    pub fn crop(mut self, rect: Rect<usize>) -> Self {
+        self.view = Some(rect);
+        self
+    }
+
+    /// Force colors on or off.
+    pub fn colored(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    pub fn print(&self) {
+        print!("{}", self);
+    }
+
+    /// Get the part of the grid to render, if any.
+    fn bounds(&self) -> Option<Rect<usize>> {
+        let all = Rect::try_from(self.grid.dim()).ok()?;
+        match self.view {
+            Some(view) => view.intersection(&all),
+            None => Some(all),
+        }
+    }
+
+    fn render_cell(&self, f: &mut fmt::Formatter, point: IdxPoint) -> fmt::Result {
+        let mut glyph = None;
+        let mut color = None;
+        let mut bg = None;
+
+        for layer in &self.layers {
+            if let Some(mark) = layer.marks.get(&point) {
+                glyph = mark.glyph.or(layer.glyph).or(glyph);
+                color = mark.color.or(layer.color).or(color);
+                bg = layer.bg.or(bg);
+            }
+        }
+
+        let text = match glyph {
+            Some(glyph) => glyph.to_string(),
+            None => self.grid[point].to_string(),
+        };
+
+        // write the escape codes directly, since `colored` would also apply
+        // its own terminal check and could drop colors the caller asked for
+        let codes: Vec<_> = [color.map(|c| c.to_fg_str()), bg.map(|c| c.to_bg_str())]
+            .into_iter()
+            .flatten()
+            .collect();
+        if self.colored && !codes.is_empty() {
+            write!(f, "\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+        } else {
+            write!(f, "{}", text)
+        }
+    }
+}
+
+impl<'a, T> fmt::Display for Renderer<'a, T>
+where T: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(Rect { min: Point(row0, col0), max: Point(row1, col1) }) = self.bounds() {
+            for row in row0..=row1 {
+                for col in col0..=col1 {
+                    self.render_cell(f, Point(row, col))?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Vec2D;
+
+    fn grid() -> Vec2D<char> {
+        Vec2D::from_rows([
+            vec!['.', '.', '#', '.'],
+            vec!['.', '#', '.', '.'],
+            vec!['.', '.', '.', '#'],
+        ])
+    }
+
+    #[test]
+    fn render_plain() {
+        let grid = grid();
+        let out = Renderer::new(&grid).colored(false).to_string();
+        assert_eq!("..#.\n.#..\n...#\n", out);
+    }
+
+    #[test]
+    fn render_layers() {
+        let grid = grid();
+        let out = Renderer::new(&grid)
+            .layer(Layer::points([Point(1, 1), Point(1, 2)]).glyph('O'))
+            .layer(Layer::chars([(Point(1, 2), '>'), (Point(3, 3), 'v')]))
+            .layer(Layer::points([Point(2, 2)]).color(Color::Red))
+            .colored(false)
+            .to_string();
+        assert_eq!("O>#.\n.#..\n..v#\n", out);
+    }
+
+    #[test]
+    fn render_window() {
+        let grid = grid();
+        let out = Renderer::new(&grid).window(Point(1, 4), 1).colored(false).to_string();
+        assert_eq!("#.\n..\n", out);
+
+        let out = Renderer::new(&grid).crop(Rect::new(Point(2, 2), Point(3, 9))).colored(false).to_string();
+        assert_eq!("#..\n..#\n", out);
+
+        let out = Renderer::new(&grid).crop(Rect::new(Point(5, 5), Point(6, 6))).colored(false).to_string();
+        assert_eq!("", out);
+    }
+
+    #[test]
+    fn render_colored() {
+        let grid = grid();
+        let out = Renderer::new(&grid)
+            .layer(Layer::points([Point(1, 1)]).color(Color::Red))
+            .layer(Layer::colors([(Point(1, 2), Color::Green)]).on_color(Color::Blue))
+            .crop(Rect::new(Point(1, 1), Point(1, 3)))
+            .colored(true)
+            .to_string();
+        assert_eq!("\x1b[31m.\x1b[0m\x1b[32;44m.\x1b[0m#\n", out);
+    }
+}